@@ -0,0 +1,211 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// bench.rs: 'aoc24 bench', which runs each entry of a runsheet several times and reports min/median/max
+//
+// The medians can be saved to a baseline file, and later runs compared against it, so we can tell whether
+// a change (e.g. tuning STACKSTACK64_MAX) really helps. The baseline uses the same line format as the manifest:
+//   solver | input file | parse ms | part 1 ms | part 2 ms | params (if any, e.g. w=7 h=7)
+// An entry is only compared with a baseline for the same solver, input file and params.
+
+use crate::error::{AocError, AocResult};
+use crate::manifest::Entry;
+use crate::solver::{Params, Parts, Registry, Timings};
+
+pub struct BenchOptions {
+    pub repeat: usize,
+    pub baseline: String,
+    pub save_baseline: bool,
+    pub threshold: f64,         // percent slower than the baseline to count as a regression
+    pub parts: Parts,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            repeat: 5,
+            baseline: "bench-baseline.txt".to_string(),
+            save_baseline: false,
+            threshold: 10.0,
+            parts: Parts::Both,
+        }
+    }
+}
+
+// differences smaller than this (in ms) are just noise
+const NOISE_MS: f64 = 0.1;
+
+// the name of each stage, and how to get its time
+type Stage = (&'static str, fn(&Timings) -> f64);
+const STAGES: [Stage; 4] = [
+    ("parse", |t| t.parse),
+    ("part 1", |t| t.part1),
+    ("part 2", |t| t.part2),
+    ("total", |t| t.total()),
+];
+
+struct BaselineEntry {
+    solver: String,
+    fname: String,
+    params: String,         // k=v, space separated and in order of k
+    medians: Timings,
+    line: usize,            // line in the baseline file
+}
+
+struct Baseline {
+    lines: Vec<String>,
+    entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    fn load(path: &str) -> AocResult<Self> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self { lines: vec!["# aoc24 bench baseline (median ms)".to_string(), "# solver | input file | parse | part 1 | part 2 | params".to_string()], entries: vec![] });
+            },
+            Err(e) => return Err(AocError::new(format!("unable to read {}: {}", path, e))),
+        };
+        let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+        let mut entries = vec![];
+        for (y, l) in lines.iter().enumerate() {
+            let t = l.trim();
+            if t.is_empty() || t.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = t.split('|').map(|f| f.trim()).collect();
+            if fields.len() != 5 && fields.len() != 6 {
+                return Err(AocError::in_line(y, "expected 'solver | input file | parse | part 1 | part 2 | params (optional)'"));
+            }
+            let ms = |i: usize| fields[i].parse::<f64>().map_err(|_| AocError::in_line(y, format!("expected a time in ms, found '{}'", fields[i])));
+            entries.push(BaselineEntry {
+                solver: fields[0].to_string(),
+                fname: fields[1].to_string(),
+                params: fields.get(5).copied().unwrap_or_default().to_string(),
+                medians: Timings { parse: ms(2)?, part1: ms(3)?, part2: ms(4)? },
+                line: y,
+            });
+        }
+        Ok(Self { lines, entries })
+    }
+
+    fn get(&self, solver: &str, fname: &str, params: &str) -> Option<&Timings> {
+        self.entries.iter().find(|e| e.solver == solver && e.fname == fname && e.params == params).map(|e| &e.medians)
+    }
+
+    fn set(&mut self, solver: &str, fname: &str, params: &str, t: Timings) {
+        let mut line = format!("{:9} | {:12} | {:.4} | {:.4} | {:.4}", solver, fname, t.parse, t.part1, t.part2);
+        if !params.is_empty() {
+            line += &format!(" | {}", params);
+        }
+        if let Some(e) = self.entries.iter_mut().find(|e| e.solver == solver && e.fname == fname && e.params == params) {
+            e.medians = t;
+            self.lines[e.line] = line;
+        } else {
+            self.entries.push(BaselineEntry { solver: solver.to_string(), fname: fname.to_string(), params: params.to_string(), medians: t, line: self.lines.len() });
+            self.lines.push(line);
+        }
+    }
+
+    fn save(&self, path: &str) -> std::io::Result<()> {
+        let mut s = self.lines.join("\n");
+        s.push('\n');
+        std::fs::write(path, s)
+    }
+}
+
+// the params, as they are written in the baseline (in order, so the same params always match)
+fn params_key(params: &Params) -> String {
+    let mut kv: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    kv.sort();
+    kv.join(" ")
+}
+
+// min, median and max
+fn stats(mut v: Vec<f64>) -> (f64, f64, f64) {
+    v.sort_by(|a, b| a.total_cmp(b));
+    let n = v.len();
+    let median = if n % 2 == 1 { v[n / 2] } else { (v[n / 2 - 1] + v[n / 2]) / 2.0 };
+    (v[0], median, v[n - 1])
+}
+
+// returns the number of regressions found (or an error with the baseline file)
+pub fn bench(registry: &Registry, runsheet: &[Entry], cli_params: &Params, opts: &BenchOptions) -> AocResult<usize> {
+    let mut baseline = Baseline::load(&opts.baseline).map_err(|e| AocError::new(format!("error in baseline {}: {}", opts.baseline, e)))?;
+    let mut regressions = 0;
+
+    // the solvers' diagnostics would be repeated for every run, so we turn them off
+    let sink = crate::log::sink();
+    crate::log::set_sink(crate::log::Sink::Off);
+
+    println!("\n--[ benchmark: {} runs each, times in ms ]--------------------", opts.repeat);
+    println!("{:10} {:13} {:7} {:>10} {:>10} {:>10} {:>10}", "solver", "input", "stage", "min", "median", "max", "baseline");
+
+    for entry in runsheet.iter() {
+        let Some(solver) = registry.find(&entry.sel) else {
+            println!("{:10} {:13} unknown day or solver", entry.sel, entry.fname);
+            continue;
+        };
+        let mut params = Params::new(entry.params.clone());
+        params.merge(cli_params, Some(solver.params()));
+        let key = params_key(&params);
+
+        let mut runs: Vec<Timings> = vec![];
+        let mut error = None;
+        for _ in 0..opts.repeat.max(1) {
+            match entry.run(solver, &params, opts.parts) {
+                Ok((_, t)) => runs.push(t),
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                },
+            }
+        }
+        if let Some(e) = error {
+            println!("{:10} {:13} error: {}", solver.name(), entry.fname, e);
+            continue;
+        }
+
+        let base = baseline.get(solver.name(), &entry.fname, &key).copied();
+        let mut medians = Timings::default();
+        for (i, (stage, f)) in STAGES.iter().enumerate() {
+            let (min, median, max) = stats(runs.iter().map(f).collect());
+            match i {
+                0 => medians.parse = median,
+                1 => medians.part1 = median,
+                2 => medians.part2 = median,
+                _ => {},
+            }
+            let (name, fname) = if i == 0 { (solver.name(), entry.fname.as_str()) } else { ("", "") };
+            let mut line = format!("{:10} {:13} {:7} {:10.3} {:10.3} {:10.3}", name, fname, stage, min, median, max);
+            if let Some(b) = base.as_ref().map(f) {
+                let change = if b > 0.0 { (median - b) / b * 100.0 } else { 0.0 };
+                line += &format!(" {:10.3} {:+5.0}%", b, change);
+                if (median - b).abs() > NOISE_MS && change.abs() > opts.threshold {
+                    if change > 0.0 {
+                        line += " ❌ slower";
+                        if i == 3 {
+                            regressions += 1;
+                        }
+                    } else {
+                        line += " ✔️ faster";
+                    }
+                }
+            }
+            println!("{}", line);
+        }
+        if opts.save_baseline {
+            baseline.set(solver.name(), &entry.fname, &key, medians);
+        }
+    }
+
+    crate::log::set_sink(sink);
+
+    if opts.save_baseline {
+        match baseline.save(&opts.baseline) {
+            Ok(()) => println!("\nbaseline saved to {}", opts.baseline),
+            Err(e) => println!("\nunable to save {}: {}", opts.baseline, e),
+        }
+    }
+    Ok(regressions)
+}
@@ -0,0 +1,85 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// cost.rs: what each move costs, for the path searches in solve.rs
+//
+// The reindeer of day16 pay 1 per step and 1000 per turn. A robot in a warehouse might instead pay more to cross
+// some kinds of floor, be able to move diagonally, or be unable to turn right around.
+// Directions are indexes into NDIRS (U, UR, R, DR, D, DL, L, UL), so a 90 degree turn is 2 apart.
+
+use std::collections::BTreeMap;
+use crate::error::{AocError, AocResult};
+use crate::solver::{Param, Params};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CostModel {
+    pub step: u64,                  // per step (orthogonal)
+    pub turn: u64,                  // per 90 degrees turned before a step (45 degrees costs half)
+    pub diagonal: Option<u64>,      // per diagonal step, if diagonal steps are allowed (they may pass between two walls)
+    pub reverse: bool,              // whether we can turn right around
+    pub terrain: BTreeMap<u8, u64>, // extra cost to step onto a cell of this kind
+}
+
+// the parameters for a cost model, for solvers that take one (the defaults are the reindeer's)
+pub const PARAMS: &[Param] = &[
+    Param { name: "step", default: "1", help: "cost of each step" },
+    Param { name: "turn", default: "1000", help: "cost of each 90 degree turn" },
+    Param { name: "diagonal", default: "no", help: "cost of a diagonal step, or 'no' for no diagonal steps" },
+    Param { name: "reverse", default: "yes", help: "whether we can turn right around (yes or no)" },
+    Param { name: "terrain", default: "", help: "extra cost to step onto a kind of cell, e.g. ~5,^20" },
+];
+
+impl CostModel {
+    pub fn reindeer() -> Self {
+        Self { step: 1, turn: 1000, diagonal: None, reverse: true, terrain: BTreeMap::new() }
+    }
+
+    pub fn from_params(params: &Params) -> AocResult<Self> {
+        let diagonal = match params.get::<String>("diagonal")?.as_str() {
+            "no" | "" => None,
+            d => Some(d.parse::<u64>().ok().filter(|&c| c >= 1).ok_or_else(|| AocError::new(format!("diagonal: expected a cost of at least 1 or 'no', found '{}'", d)))?),
+        };
+        // a step that costs nothing would let the searches go round in circles for free
+        let step: u64 = params.get("step")?;
+        if step < 1 {
+            return Err(AocError::new(format!("step: expected a cost of at least 1, found {}", step)));
+        }
+        let reverse = match params.get::<String>("reverse")?.as_str() {
+            "yes" | "true" => true,
+            "no" | "false" => false,
+            r => return Err(AocError::new(format!("reverse: expected yes or no, found '{}'", r))),
+        };
+        let mut terrain = BTreeMap::new();
+        for t in params.get::<String>("terrain")?.split(',').filter(|t| !t.is_empty()) {
+            let mut chars = t.chars();
+            let c = chars.next().filter(|c| c.is_ascii() && !"#.SE".contains(*c));
+            let cost = chars.as_str().parse::<u64>().ok();
+            let (Some(c), Some(cost)) = (c, cost) else {
+                return Err(AocError::new(format!("terrain: expected a kind of cell then its cost (e.g. ~5), found '{}'", t)));
+            };
+            terrain.insert(c as u8, cost);
+        }
+        Ok(Self { step, turn: params.get("turn")?, diagonal, reverse, terrain })
+    }
+
+    // the kinds of cell (other than walls and open space) the level may have
+    pub fn terrain_chars(&self) -> String {
+        self.terrain.keys().map(|&c| c as char).collect()
+    }
+
+    // the directions we can step in
+    pub fn dirs(&self) -> impl Iterator<Item = usize> {
+        (0..8).step_by(if self.diagonal.is_some() { 1 } else { 2 })
+    }
+
+    // the cost to step in direction to, when facing direction from, onto a cell of this kind (None if we can't)
+    pub fn move_cost(&self, from: usize, to: usize, cell: u8) -> Option<u64> {
+        let eighths = (from + 8 - to) % 8;
+        let eighths = eighths.min(8 - eighths) as u64;
+        if eighths == 4 && !self.reverse {
+            return None;
+        }
+        let step = if to.is_multiple_of(2) { self.step } else { self.diagonal? };
+        Some(step + self.turn * eighths / 2 + self.terrain.get(&cell).copied().unwrap_or(0))
+    }
+}
@@ -1,57 +1,86 @@
-pub fn day01(input: &String) -> (String,String) {
-    // split input by whitespace, and convert to unsigned integers
-    let input: Vec<usize> = input.split_whitespace().map(|s| s.parse::<usize>().expect("Input should be unsigned integers")).collect();
-
-    // two seperate vecs for left column and right column
-    // could also use transpose, but that's not in std
-    let mut a = Vec::<usize>::new();
-    let mut b = Vec::<usize>::new();
-
-    // split input into the two seperate vecs
-    input.into_iter().enumerate().for_each(|(i,n)| {
-        if i%2==0 {
-            a.push(n);
-        } else {
-            b.push(n);
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
+
+pub struct Day01;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day01);
+}
+
+impl Solver for Day01 {
+    type Input = (Vec<usize>, Vec<usize>);
+
+    fn name(&self) -> &'static str { "day01" }
+    fn day(&self) -> usize { 1 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // split input by whitespace, and convert to unsigned integers
+        let mut nums: Vec<usize> = vec![];
+        for (y,line) in input.lines().enumerate() {
+            for (x,s) in tokens(line) {
+                nums.push(parse_at(s, y, x)?);
+            }
         }
-    });
-
-    // sort!
-    a.sort();
-    b.sort();
-
-    // find differences and sum them
-    let sum = a.iter().enumerate().map(|(i,n)| n.abs_diff(b[i])).sum::<usize>();
-
-    // solution to part one
-    println!("sum: {sum}");
-
-    // part two
-
-    // to start, count how often each number appears in list b
-    // store the count result in a BTreeMap for easy access
-    let mut map = std::collections::BTreeMap::<usize,usize>::new();
-    b.into_iter().for_each(|n| {
-        // if key exists, add to its value. otherwise, insert it with a value of 1
-        match map.get_key_value(&n) {
-            Some((&k,&v)) => {
-                map.insert(k, v+1);
-            },
-            None => {
-                map.insert(n, 1);
-            },
-        };
-    });
-
-    // now iterate through list a, collecting 'similarity scores', and total them
-    let score: usize = a.iter().map(|n| {
-        match map.get_key_value(&n) {
-            Some((&k,&v)) => k * v,
-            None => 0,
+        if nums.len() % 2 != 0 {
+            return Err(AocError::new("expected two columns of numbers, but found an odd count"));
         }
-    }).sum();
 
-    // solution to part two
-    println!("score: {score}");
-    (sum.to_string(), score.to_string())
-}
\ No newline at end of file
+        // two seperate vecs for left column and right column
+        // could also use transpose, but that's not in std
+        let mut a = Vec::<usize>::new();
+        let mut b = Vec::<usize>::new();
+
+        // split input into the two seperate vecs
+        nums.into_iter().enumerate().for_each(|(i,n)| {
+            if i%2==0 {
+                a.push(n);
+            } else {
+                b.push(n);
+            }
+        });
+
+        // sort!
+        a.sort();
+        b.sort();
+
+        Ok((a, b))
+    }
+
+    fn part1(&self, (a, b): &mut Self::Input) -> AocResult<String> {
+        // find differences and sum them
+        let sum = a.iter().enumerate().map(|(i,n)| n.abs_diff(b[i])).sum::<usize>();
+
+        // solution to part one
+        info!("sum: {sum}");
+        Ok(sum.to_string())
+    }
+
+    fn part2(&self, (a, b): &mut Self::Input) -> AocResult<String> {
+        // to start, count how often each number appears in list b
+        // store the count result in a BTreeMap for easy access
+        let mut map = std::collections::BTreeMap::<usize,usize>::new();
+        b.iter().for_each(|&n| {
+            // if key exists, add to its value. otherwise, insert it with a value of 1
+            match map.get_key_value(&n) {
+                Some((&k,&v)) => {
+                    map.insert(k, v+1);
+                },
+                None => {
+                    map.insert(n, 1);
+                },
+            };
+        });
+
+        // now iterate through list a, collecting 'similarity scores', and total them
+        let score: usize = a.iter().map(|n| {
+            match map.get_key_value(&n) {
+                Some((&k,&v)) => k * v,
+                None => 0,
+            }
+        }).sum();
+
+        // solution to part two
+        info!("score: {score}");
+        Ok(score.to_string())
+    }
+}
@@ -1,75 +1,98 @@
-pub fn day02(input: &String) -> (String,String) {
-    // split input by whitespace, and convert to unsigned integers
-    let reports = input.split('\n').collect::<Vec<&str>>();
-    let reports: Vec<Vec<usize>> = reports.into_iter().map(|r| r.split_whitespace().collect::<Vec<&str>>().into_iter().map(|s| s.parse::<usize>().expect("Input should be unsigned integers")).collect()).collect();
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
-    // determine if report is safe according to rules
-    fn is_safe(r: &Vec<usize>) -> bool {
-        let up = if r[0] < r[1] {
-            true
+pub struct Day02;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day02);
+}
+
+// determine if report is safe according to rules
+fn is_safe(r: &Vec<usize>) -> bool {
+    let up = if r[0] < r[1] {
+        true
+    } else {
+        false
+    };
+    for i in 0..r.len()-1 {
+        if r[i] == r[i+1] {
+            return false;
+        }
+        if up {
+            if r[i] > r[i+1] {
+                return false;
+            }
+            let d = r[i+1] - r[i];
+            if d > 3 {
+                return false;
+            }
         } else {
-            false
-        };
-        for i in 0..r.len()-1 {
-            if r[i] == r[i+1] {
+            if r[i] < r[i+1] {
                 return false;
             }
-            if up {
-                if r[i] > r[i+1] {
-                    return false;
-                }
-                let d = r[i+1] - r[i];
-                if d > 3 {
-                    return false;
-                }
-            } else {
-                if r[i] < r[i+1] {
-                    return false;
-                }
-                let d = r[i] - r[i+1];
-                if d > 3 {
-                    return false;
-                }
+            let d = r[i] - r[i+1];
+            if d > 3 {
+                return false;
             }
         }
+    }
 
-        true
+    true
+}
+
+fn create_variants(r: &Vec<usize>) -> Vec<Vec<usize>> {
+    let mut vs = vec![];
+    for i in 0..r.len() {
+        let mut v = r.clone();
+        v.remove(i);
+        vs.push(v);
     }
+    vs
+}
 
-    // how many reports are safe
-    let safe = reports.iter().filter(|&r| is_safe(r)).count();
+impl Solver for Day02 {
+    type Input = Vec<Vec<usize>>;
 
-    println!("safe: {safe}");
+    fn name(&self) -> &'static str { "day02" }
+    fn day(&self) -> usize { 2 }
 
-    // part two
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // split input by whitespace, and convert to unsigned integers
+        input.lines().enumerate().filter(|(_,r)| !r.trim().is_empty()).map(|(y,r)| {
+            let report = tokens(r).map(|(x,s)| parse_at::<usize>(s, y, x)).collect::<AocResult<Vec<usize>>>()?;
+            if report.len() < 2 {
+                return Err(AocError::in_line(y, "a report needs at least two levels"));
+            }
+            Ok(report)
+        }).collect()
+    }
 
-    fn create_variants(r: &Vec<usize>) -> Vec<Vec<usize>> {
-        let mut vs = vec![];
-        for i in 0..r.len() {
-            let mut v = r.clone();
-            v.remove(i);
-            vs.push(v);
-        }
-        vs
+    fn part1(&self, reports: &mut Self::Input) -> AocResult<String> {
+        // how many reports are safe
+        let safe = reports.iter().filter(|&r| is_safe(r)).count();
+
+        info!("safe: {safe}");
+        Ok(safe.to_string())
     }
 
-    let mut safe2 = 0;
-    for r in reports.iter() {
-        if is_safe(&r) {
-            safe2 += 1;
-            continue;
-        }
-        // create variants of the report
-        let vs = create_variants(&r);
-        for v in vs.iter() {
-            if is_safe(&v) {
+    fn part2(&self, reports: &mut Self::Input) -> AocResult<String> {
+        let mut safe2 = 0;
+        for r in reports.iter() {
+            if is_safe(&r) {
                 safe2 += 1;
-                break;
+                continue;
+            }
+            // create variants of the report
+            let vs = create_variants(&r);
+            for v in vs.iter() {
+                if is_safe(&v) {
+                    safe2 += 1;
+                    break;
+                }
             }
         }
-    }
-
-    println!("part two: {safe2}");
 
-    (safe.to_string(), safe2.to_string())
-}
\ No newline at end of file
+        info!("part two: {safe2}");
+        Ok(safe2.to_string())
+    }
+}
@@ -1,72 +1,66 @@
-pub fn day04(input: &String) -> (String,String) {
-    // word search! for XMAS
-    // get text as a grid of chars
-    let rows = input.lines().collect::<Vec<&str>>();
-    let data: Vec<Vec<char>> = rows.iter().map(|r| r.chars().collect::<Vec<char>>()).collect();
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
+use crate::grid::{*};
 
-    let h = rows.len();
-    let w = rows[0].len();
-    println!("w: {w} h: {h}");
-    let mut c = 0;
+pub struct Day04;
 
-    const XMAS: [char; 4] = [ 'X', 'M', 'A', 'S' ];
-    const SAMX: [char; 4] = [ 'S', 'A', 'M', 'X' ];
+pub fn register(r: &mut Registry) {
+    r.add(Day04);
+}
 
-    for y in 0..h {
-        for x in 0..w {
-            // horizontal search
-            if x < w-3 {
-                let window = &data[y][x..x+4];
-                c += (window == XMAS) as usize;
-                c += (window == SAMX) as usize;
-            }
-            // vertical search
-            if y < h-3 {
-                let window = [ data[y][x], data[y+1][x], data[y+2][x], data[y+3][x] ];
-                c += (window == XMAS) as usize;
-                c += (window == SAMX) as usize;
-            }
-            // diagonal TL--BR search
-            if x < w-3 && y < h-3 {
-                let window = [ data[y][x], data[y+1][x+1], data[y+2][x+2], data[y+3][x+3] ];
-                c += (window == XMAS) as usize;
-                c += (window == SAMX) as usize;
-            }
-            // diagonal TR--BL search
-            if x >= 3 && y < h-3 {
-                let window = [ data[y][x], data[y+1][x-1], data[y+2][x-2], data[y+3][x-3] ];
-                c += (window == XMAS) as usize;
-                c += (window == SAMX) as usize;
-            }
+impl Solver for Day04 {
+    type Input = Grid;
+
+    fn name(&self) -> &'static str { "day04" }
+    fn day(&self) -> usize { 4 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // word search! for XMAS
+        // get text as a grid (which checks it is rectangular)
+        if input.lines().next().is_none() {
+            return Err(AocError::new("empty word search"));
         }
+        Grid::from_str(input)
     }
 
-    println!("part one count: {c}");
+    fn part1(&self, grid: &mut Self::Input) -> AocResult<String> {
+        debug!("w: {} h: {}", grid.w, grid.h);
 
-    // part two: X-MAS
-    // a..         ..a
-    // .A.   and   .A.
-    // ..b         b..
-    // the pattern is valid for specific values of a and b, either MS or SM
+        // read every line across the grid, in all eight directions (so backwards and diagonal too)
+        let mut c = 0;
+        for dir in NDIRS {
+            for line in grid.lines(dir) {
+                let text: Vec<u8> = line.map(|(_, &b)| b).collect();
+                c += text.windows(4).filter(|w| w == b"XMAS").count();
+            }
+        }
 
-    const MS: [char; 2] = ['M','S'];
-    const SM: [char; 2] = ['S','M'];
+        info!("part one count: {c}");
+        Ok(c.to_string())
+    }
 
-    let mut c2 = 0;
+    fn part2(&self, grid: &mut Self::Input) -> AocResult<String> {
+        // part two: X-MAS
+        // a..         ..a
+        // .A.   and   .A.
+        // ..b         b..
+        // the pattern is valid for specific values of a and b, either MS or SM
 
-    for y in 1..h-1 {
-        for x in 1..w-1 {
-            if data[y][x] == 'A' {
-                let mut pass_count = 0;
-                let window = [ data[y-1][x-1], data[y+1][x+1] ];
-                pass_count += (window == MS || window == SM) as usize;      // test TL-BR
-                let window = [ data[y-1][x+1], data[y+1][x-1] ];            // test TR-BL
-                pass_count += (window == MS || window == SM) as usize;
-                c2 += (pass_count == 2) as usize;       // increment if this X passes both tests
-            }
+        const MS: [Option<u8>; 2] = [Some(b'M'), Some(b'S')];
+        const SM: [Option<u8>; 2] = [Some(b'S'), Some(b'M')];
+
+        let mut c2 = 0;
+        for xy in grid.find(b'A') {
+            let n = grid.get_neighbours(&xy);
+            let mut pass_count = 0;
+            let window = [ n[NDIR_UL], n[NDIR_DR] ];                     // test TL-BR
+            pass_count += (window == MS || window == SM) as usize;
+            let window = [ n[NDIR_UR], n[NDIR_DL] ];                     // test TR-BL
+            pass_count += (window == MS || window == SM) as usize;
+            c2 += (pass_count == 2) as usize;       // increment if this X passes both tests
         }
-    }
 
-    println!("part two count: {c2}");
-    (c.to_string(), c2.to_string())
-}
\ No newline at end of file
+        info!("part two count: {c2}");
+        Ok(c2.to_string())
+    }
+}
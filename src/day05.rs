@@ -1,73 +1,113 @@
-pub fn day05(input: &String) -> (String,String) {
-    // split input into rules and updates
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
-    let split_point = input.find("\n\n").expect("double newline");
+pub struct Day05;
 
-    // find returns the byte offset, we are using it as a char offset, luckily the input is ascii :)
+pub fn register(r: &mut Registry) {
+    r.add(Day05);
+}
 
-    let rules: Vec<(usize,usize)> = input[0..split_point].split_whitespace().map(
-        |s| ( s[0..2].parse::<usize>().expect("2 digit number"), s[3..5].parse::<usize>().expect("2 digit number") )
-    ).collect();
+pub struct Input {
+    rules: Vec<(usize,usize)>,
+    updates: Vec<Vec<usize>>,
+}
 
-    let updates: Vec<Vec<usize>> = input[split_point+2..].split_whitespace().map(
-        |u| u.split(',').map(
-            |s| s.parse::<usize>().expect("number")
-        ).collect()
-    ).collect();
+// rules: a|b means a must be before b
 
-    // rules: a|b means a must be before b
+fn invalid_pair(a: usize, b: usize, rules: &Vec<(usize,usize)>) -> bool {
+    // check if this pair is valid according to the rules
+    // find matching rules
+    let rf: Vec<&(usize,usize)> = rules.iter().filter(|r| (r.0 == a && r.1 == b) || (r.0 == b && r.1 == a)).collect();
 
-    fn invalid_pair(a: usize, b: usize, rules: &Vec<(usize,usize)>) -> bool {
-        // check if this pair is valid according to the rules
-        // find matching rules
-        let rf: Vec<&(usize,usize)> = rules.iter().filter(|r| (r.0 == a && r.1 == b) || (r.0 == b && r.1 == a)).collect();
+    // check for any failures
+    rf.iter().any( |r| !(r.0 == a && r.1 == b) )
+}
 
-        // check for any failures
-        rf.iter().any( |r| !(r.0 == a && r.1 == b) )
+fn invalid_update(u: &Vec<usize>, rules: &Vec<(usize,usize)>) -> bool {
+    let mut f = false;
+    for j in 0..u.len()-1 {             // check each number pair in the update
+        f |= invalid_pair(u[j], u[j+1], rules);
     }
+    f
+}
 
-    let mut sum = 0;
-    let mut failed_updates: Vec<Vec<usize>> = vec![];   // keep the failed updates for part two
+impl Solver for Day05 {
+    type Input = Input;
 
-    for i in 0..updates.len() {
-        let u = &updates[i];
+    fn name(&self) -> &'static str { "day05" }
+    fn day(&self) -> usize { 5 }
 
-        let mut f = false;
-        for j in 0..u.len()-1 {             // check each number pair in the update
-            f |= invalid_pair(u[j], u[j+1], &rules);
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // split input into rules and updates
+
+        let lines = input.lines().collect::<Vec<&str>>();
+        let Some(split_point) = lines.iter().position(|l| l.trim().is_empty()) else {
+            return Err(AocError::new("expected a blank line between the rules and the updates"));
+        };
+
+        let mut rules: Vec<(usize,usize)> = vec![];
+        for (y,s) in lines[..split_point].iter().enumerate() {
+            let Some(bar) = s.find('|') else {
+                return Err(AocError::in_line(y, "expected a rule 'a|b'"));
+            };
+            rules.push(( parse_at(&s[..bar], y, 0)?, parse_at(&s[bar+1..], y, bar+1)? ));
         }
-        if f {
-            //println!("update invalid: {0:?}", updates[i]);
-            failed_updates.push(updates[i].clone());
-        } else {
-            //println!("update valid  : {0:?}", updates[i]);
-            sum += u[u.len()/2];            // find middle pair and add to sum
+
+        let mut updates: Vec<Vec<usize>> = vec![];
+        for (y,u) in lines.iter().enumerate().skip(split_point+1).filter(|(_,u)| !u.trim().is_empty()) {
+            let mut update: Vec<usize> = vec![];
+            let mut x = 0;
+            for s in u.split(',') {
+                update.push(parse_at(s, y, x)?);
+                x += s.len() + 1;
+            }
+            updates.push(update);
         }
-    }
 
-    println!("part one sum: {sum}");
+        Ok(Input { rules, updates })
+    }
 
-    // part two... fix and sum only the incorrect updates
-    let mut sum2 = 0;
-    for u in failed_updates.iter_mut() {
-        //println!("original: {u:?}");
+    fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+        let mut sum = 0;
 
-        // sort with custom rules
-        // note it doesn't actually matter which way we sort -- as we are using just the middle value!
-        u.sort_by(|a,b| {
-            match invalid_pair(*a,*b,&rules) {
-                false => std::cmp::Ordering::Less,
-                true => std::cmp::Ordering::Greater,
+        for u in input.updates.iter() {
+            if invalid_update(u, &input.rules) {
+                trace!("update invalid: {0:?}", u);
+            } else {
+                trace!("update valid  : {0:?}", u);
+                sum += u[u.len()/2];            // find middle pair and add to sum
             }
-        });
-
-        //println!("sorted  : {u:?}");
+        }
 
-        // find middle pair and add to sum
-        sum2 += u[u.len()/2];
+        info!("part one sum: {sum}");
+        Ok(sum.to_string())
     }
 
-    println!("part two sum: {sum2}");
+    fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+        // part two... fix and sum only the incorrect updates
+        let rules = &input.rules;
+        let mut failed_updates: Vec<Vec<usize>> = input.updates.iter().filter(|u| invalid_update(u, rules)).cloned().collect();
+
+        let mut sum2 = 0;
+        for u in failed_updates.iter_mut() {
+            trace!("original: {u:?}");
+
+            // sort with custom rules
+            // note it doesn't actually matter which way we sort -- as we are using just the middle value!
+            u.sort_by(|a,b| {
+                match invalid_pair(*a,*b,rules) {
+                    false => std::cmp::Ordering::Less,
+                    true => std::cmp::Ordering::Greater,
+                }
+            });
+
+            trace!("sorted  : {u:?}");
 
-    (sum.to_string(), sum2.to_string())
-}
\ No newline at end of file
+            // find middle pair and add to sum
+            sum2 += u[u.len()/2];
+        }
+
+        info!("part two sum: {sum2}");
+        Ok(sum2.to_string())
+    }
+}
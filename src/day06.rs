@@ -1,190 +1,207 @@
-pub fn day06(input: &String) -> (String,String) {
-    // where does the guard go?
-    // read the input into a vec<vec<char>>
-    let rows = input.lines().collect::<Vec<&str>>();
-    let mut data: Vec<Vec<char>> = rows.iter().map(|r| r.chars().collect::<Vec<char>>()).collect();
-
-    let h = rows.len();
-    let w = rows[0].len();
-
-    println!("part 1 calculating...");
-
-    // define directions using a bitmask
-    const UP: u8 = 1u8;
-    const RIGHT: u8 = 2u8;
-    const DOWN: u8 = 4u8;
-    const LEFT: u8 = 8u8;
-
-    // where is the guard to start?
-    let mut gx = -1;
-    let mut gy = -1;
-    let mut gd = 0u8;     // guard direction - 0 up, 1 right, 2 down, 3 left
-    for y in 0..h {
-        for x in 0..w {
-            if data[y][x] != '.' && data[y][x] != '#' {
-                gx = x as isize;
-                gy = y as isize;
-                gd = match data[y][x] {
-                    '^' => UP,
-                    '>' => RIGHT,
-                    'v' => DOWN,
-                    '<' => LEFT,
-                    _ => panic!("cannot recognise guard direction"),
-                };
-                break;
-            }
-        }
-        if gx != -1 {
-            break;
-        }
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
+use crate::grid::Grid;
+use crate::vector::Vector;
+use crate::render::{self, Animation, Palette, Picture};
+use crossterm::style::Color;
+
+pub struct Day06;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day06);
+}
+
+// define directions using a bitmask
+const UP: u8 = 1u8;
+const RIGHT: u8 = 2u8;
+const DOWN: u8 = 4u8;
+const LEFT: u8 = 8u8;
+
+pub struct Input {
+    grid: Grid,
+    gs: Vector,             // guard start location
+    gds: u8,                // guard start direction
+}
+
+// given a direction, return a delta (x, y)
+fn map_dir(d: u8) -> Vector {
+    match d {
+        UP => Vector::new(0,-1),
+        RIGHT => Vector::new(1,0),
+        DOWN => Vector::new(0,1),
+        LEFT => Vector::new(-1,0),
+        _ => panic!("invalid direction"),
     }
-    if gx == -1 {
-        panic!("failed to locate guard");
+}
+
+// the map, with the positions visited shaded, and the guard (if still on the map)
+fn picture(input: &Input, visited: &Grid<u8>, guard: Option<(Vector, u8)>) -> Picture {
+    let mut pic = Picture::from_grid(&input.grid, &Palette::default());
+    pic.shade(&visited.find_fn(|&d| d != 0), Color::DarkMagenta);
+    if let Some((g, gd)) = guard {
+        let c = match gd { UP => '^', RIGHT => '>', DOWN => 'v', _ => '<' };
+        pic.point(g, c, Color::Green);
     }
-    //println!("gx: {gx} gy: {gy}");
-
-    // save guard start location and direction for part two
-    let gs = (gx as usize,gy as usize);
-    let gds = gd;
+    pic
+}
 
-    // remove guard from input to make things easier
-    data[gy as usize][gx as usize] = '.';
-
-    // vec to keep track of guards position and direction
-    let mut visited: Vec<Vec<u8>> = vec![];
-    for _ in 0..h {
-        let v: Vec<u8> = vec![0; w];
-        visited.push(v);
-    }
-    visited[gy as usize][gx as usize] = gd;
-
-    // given a direction, return a delta (x, y)
-    fn map_dir(d: u8) -> (isize, isize) {
-        match d {
-            UP => (0,-1),
-            RIGHT => (1,0),
-            DOWN => (0,1),
-            LEFT => (-1,0),
-            _ => panic!("invalid direction"),
-        }
-    }
+// walk the guard off the map, returning the directions walked in each position
+// (showing each step, if there's an animation)
+fn walk(input: &Input, mut anim: Option<Animation>) -> Grid<u8> {
+    let mut g = input.gs;
+    let mut gd = input.gds;
 
-    // return what is in the location, either . (nothing) or # (obstruction) or ! (out of area)
-    fn peek(x: isize, y: isize, w: usize, h: usize, data: &Vec<Vec<char>>) -> char {
-        if x < 0 || x >= w as isize || y < 0 || y >= h as isize {
-            return '!';
-        }
-        data[y as usize][x as usize]
-    }
+    // grid to keep track of guards position and direction
+    let mut visited: Grid<u8> = input.grid.same_size_with(0);
+    visited.put(&g, gd);
 
     // main walking loop
     let mut in_map = true;
     while in_map {
-        // peek next location
-        let (dx,dy) = map_dir(gd);
-        let (nx,ny) = (gx+dx,gy+dy);
-        let p = peek(nx, ny, w, h, &data);
-        match p {
-            '!' => in_map = false,      // out of map
-            '#' => {                    // obstruction, turn 90 degrees
+        if let Some(a) = anim.as_mut() {
+            a.frame(&picture(input, &visited, Some((g, gd))), &format!("step {}", a.frames()));
+        }
+        // peek next location: . (nothing) or # (obstruction) or None (out of area)
+        let n = g.add(&map_dir(gd));
+        match input.grid.get(&n) {
+            None => in_map = false,     // out of map
+            Some(b'#') => {             // obstruction, turn 90 degrees
                 gd <<= 1;
                 if gd == 16 {
                     gd = UP;
                 }
             },
-            '.' => {                    // walk the guard
-                gx = nx;                // save guards new position
-                gy = ny;
-                visited[gy as usize][gx as usize] |= gd;    // store direction guard moved in this position
+            Some(_) => {                // walk the guard
+                g = n;                  // save guards new position
+                if let Some(v) = visited.get_mut(&g) {
+                    *v |= gd;           // store direction guard moved in this position
+                }
             },
-            _ => panic!("invalid return value from peek"),
         }
     }
-
-    // how many positions the guard visited
-    let v = visited.iter().flatten().collect::<Vec<&u8>>().iter().filter(|v| ***v != 0).count();
-    //println!("part one: {v}");
-    println!("part 2 calculating...");
-
-    // part two
-    // place an obstruction on the guards existing path (i.e. in visited above) but not the starting position
-    // we want to know if it will form a loop
-    // we need to store the guard's DIRECTIONS for each position, too!
-
-    // get a list of (x,y) where we could place an obstruction
-    let sites: Vec<(usize, usize)> = visited.iter().enumerate().map(|(y,row)| {
-        row.iter().enumerate().map(|(x,d)| {
-            if *d != 0 {
-                return Some((x,y));
-            } else {
-                return None;
+    visited
+}
+
+impl Solver for Day06 {
+    type Input = Input;
+
+    fn name(&self) -> &'static str { "day06" }
+    fn day(&self) -> usize { 6 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // where does the guard go?
+        // read the input into a grid (which checks the map is rectangular)
+        let mut grid = Grid::from_str(input)?;
+
+        // check the map has only the expected characters, and a single guard
+        let mut guard: Option<(Vector, u8)> = None;
+        for (xy, &c) in grid.iter() {
+            let gd = match c {
+                b'.' | b'#' => continue,
+                b'^' => UP,
+                b'>' => RIGHT,
+                b'v' => DOWN,
+                b'<' => LEFT,
+                _ => return Err(AocError::at(xy.1 as usize, xy.0 as usize, format!("unexpected character '{}'", c as char))),
+            };
+            if guard.is_some() {
+                return Err(AocError::at(xy.1 as usize, xy.0 as usize, "more than one guard found"));
             }
-        }).collect::<Vec<Option<(usize,usize)>>>()
-    }).flatten().filter(|d| d.is_some()).map(|d| d.unwrap()).collect();
-
-    //println!("sites: {sites:?}");
-    //println!("sites len: {0:?}", sites.len());
-
-    // remove guard start position
-    let sites: Vec<&(usize, usize)> = sites.iter().filter(|&&d| d != gs).collect();
-
-    let mut looped_count = 0;       // number of times the obstruction leads to the guard walking a loop
-
-    // test each (x,y) on a input copy, with the obstruction placed, and check for a guard walk loop
-    for (x,y) in sites.iter() {
-        // print!("trying obstruction at {x:3},{y:3}: ");
-        // add an obstruction
-        let mut d = data.clone();
-        d[*y][*x] = '#';
-        // set guard location to start location
-        gx = gs.0 as isize;
-        gy = gs.1 as isize;
-        gd = gds;
-        // a 2d vec to keep track of guard positions and directions
-        let mut visited2: Vec<Vec<u8>> = vec![];
-        for _ in 0..h {
-            let v: Vec<u8> = vec![0; w];
-            visited2.push(v);
+            guard = Some((xy, gd));
         }
-        visited2[gy as usize][gx as usize] = gd;
-
-        // walk the guard
-        let mut in_map = true;
-        let mut is_loop = false;
-        while in_map && !is_loop {
-            // peek next location
-            let (dx,dy) = map_dir(gd);
-            let (nx,ny) = (gx+dx,gy+dy);
-            let p = peek(nx, ny, w, h, &d);
-            match p {
-                '!' => in_map = false,      // guard is off the map
-                '#' => {                    // turn 90 degrees
-                    gd <<= 1;
-                    if gd == 16 {
-                        gd = UP;
-                    }
-                },
-                '.' => {                    // walk the guard
-                    gx = nx;                // set guards new position
-                    gy = ny;
-                    if visited2[gy as usize][gx as usize] & gd != 0 {   // have we been here before, in this direction?
-                        is_loop = true;
-                    } else {
-                        visited2[gy as usize][gx as usize] |= gd;       // save the direction we walked to this position
-                    }
-                },
-                _ => panic!("invalid return value from peek"),
-            }
-        }
-        if is_loop {
-            //println!("looped");
-            looped_count += 1;
-        } else {
-            //println!("off map");
+        let Some((gs, gd)) = guard else {
+            return Err(AocError::new("failed to locate guard"));
+        };
+        trace!("gx: {} gy: {}", gs.0, gs.1);
+
+        // remove guard from input to make things easier
+        grid.put(&gs, b'.');
+
+        Ok(Input { grid, gs, gds: gd })
+    }
+
+    fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+        info!("part 1 calculating...");
+
+        let visited = walk(input, Animation::new());
+        if render::enabled() {
+            picture(input, &visited, None).draw("the guard's patrol:");
         }
 
+        // how many positions the guard visited
+        let v = visited.find_fn(|&d| d != 0).len();
+        info!("part one: {v}");
+        Ok(v.to_string())
     }
-    println!("part two looped_count: {looped_count}");
 
-    (v.to_string(), looped_count.to_string())
-}
\ No newline at end of file
+    fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+        info!("part 2 calculating...");
+
+        // part two
+        // place an obstruction on the guards existing path (i.e. in visited from part one) but not the starting position
+        // we want to know if it will form a loop
+        // we need to store the guard's DIRECTIONS for each position, too!
+        let gs = input.gs;
+        let visited = walk(input, None);
+
+        // get a list of (x,y) where we could place an obstruction
+        let sites: Vec<Vector> = visited.find_fn(|&d| d != 0);
+
+        trace!("sites: {sites:?}");
+        trace!("sites len: {0:?}", sites.len());
+
+        // remove guard start position
+        let sites: Vec<&Vector> = sites.iter().filter(|&&d| d != gs).collect();
+
+        let mut looped_count = 0;       // number of times the obstruction leads to the guard walking a loop
+
+        // test each (x,y) on a input copy, with the obstruction placed, and check for a guard walk loop
+        for site in sites.iter() {
+            trace!("trying obstruction at {:3},{:3}:", site.0, site.1);
+            // add an obstruction
+            let mut d = input.grid.clone();
+            d.put(site, b'#');
+            // set guard location to start location
+            let mut g = gs;
+            let mut gd = input.gds;
+            // a grid to keep track of guard positions and directions
+            let mut visited2: Grid<u8> = d.same_size_with(0);
+            visited2.put(&g, gd);
+
+            // walk the guard
+            let mut in_map = true;
+            let mut is_loop = false;
+            while in_map && !is_loop {
+                // peek next location
+                let n = g.add(&map_dir(gd));
+                match d.get(&n) {
+                    None => in_map = false,     // guard is off the map
+                    Some(b'#') => {             // turn 90 degrees
+                        gd <<= 1;
+                        if gd == 16 {
+                            gd = UP;
+                        }
+                    },
+                    Some(_) => {                // walk the guard
+                        g = n;                  // set guards new position
+                        let v = visited2.get_unchecked(&g);
+                        if v & gd != 0 {        // have we been here before, in this direction?
+                            is_loop = true;
+                        } else {
+                            visited2.put_unchecked(&g, v | gd);     // save the direction we walked to this position
+                        }
+                    },
+                }
+            }
+            if is_loop {
+                trace!("looped");
+                looped_count += 1;
+            } else {
+                trace!("off map");
+            }
+
+        }
+        info!("part two looped_count: {looped_count}");
+        Ok(looped_count.to_string())
+    }
+}
@@ -1,117 +1,153 @@
-pub fn day07(input: &String) -> (String,String) {
-    // missing operators puzzle
-
-    let rows = input.lines().collect::<Vec<&str>>();
-    let data: Vec<Vec<usize>> = rows.iter().map(|r| {
-        let mut nums: Vec<String> = r.split_whitespace().map(|s| s.to_string()).collect();  // split by whitespace
-        nums[0] = nums[0][0..nums[0].len()-1].to_string();                                  // remove colon
-        return nums.iter().map(|ns| ns.parse::<usize>().expect("number")).collect();        // convert to numbers
-    }).collect();
-
-    println!("part 1 calculating...");
-
-    // evaluate the equation left to right, operators are + or *
-    // we want to know which lines could be valid, and sum those totals
-    let valid_data: Vec<Vec<usize>> = data.clone().into_iter().filter(|r| {
-        // n.b. the target result is stored in r[0]
-
-        //print!("line: {r:?} ");
-
-        let num_ops = r.len() - 2;
-
-        // we will use a bitmap to store what operation we are performing. i.e. 0 for add, 1 for multiply.
-        // we will be done when we increment the bitmap and hit op_map_done
-        let op_map_done = 1 << (num_ops + 1);
-
-        let mut valid_line = false;
-
-        for op_map in 0..op_map_done {
-            // apply the ops specified in op_map to the numbers
-            let mut result = r[1];  // start with the leftmost number
-            for i in 2..r.len() {
-                let op = (op_map >> (i-2)) & 0x01;  // find what operation to perform
-                match op {
-                    0 => result += r[i],
-                    1 => result *= r[i],
-                    _ => panic!("invalid op"),
-                }
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
+
+pub struct Day07;
+
+// the most numbers after the colon on a line (so the operator bitmap fits in a u64)
+const MAX_NUMS: usize = 32;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day07);
+}
+
+impl Solver for Day07 {
+    type Input = Vec<Vec<usize>>;
+
+    fn name(&self) -> &'static str { "day07" }
+    fn day(&self) -> usize { 7 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // missing operators puzzle
+
+        input.lines().enumerate().filter(|(_,r)| !r.trim().is_empty()).map(|(y,r)| {
+            let Some(colon) = r.find(':') else {
+                return Err(AocError::in_line(y, "expected 'total: a b c...'"));
+            };
+            let mut nums: Vec<usize> = vec![ parse_at(r[..colon].trim(), y, 0)? ];             // the total, before the colon
+            for (x,ns) in tokens(&r[colon+1..]) {                                               // split by whitespace
+                nums.push(parse_at(ns, y, colon + 1 + x)?);                                     // convert to numbers
             }
-            // does our result match the target result?
-            if result == r[0] {
-                valid_line = true;
+            if nums.len() < 2 {
+                return Err(AocError::at(y, colon, "expected at least one number after the colon"));
             }
-        }
-        //println!("{valid_line}");
-        valid_line
-    }).collect();
-
-    let valid_sum1: usize = valid_data.iter().map(|r| r[0]).sum();
-    println!("part one sum: {valid_sum1}");
-    println!("part 2 calculating...");
-
-    // part two
-    let valid_data: Vec<Vec<usize>> = data.into_iter().filter(|r| {
-        // target is in r[0]
-        //print!("line: {r:?} ");
-
-        const OP_ADD: u8 = 0;
-        const _OP_MUL: u8 = 1;
-        const OP_JOIN: u8 = 2;
-        const OP_OVERFLOW: u8 = 3;
-
-        let num_ops = r.len() - 2;                          // number of operation slots
-        let mut ops_map: Vec<u8> = vec![OP_ADD; num_ops];   // where we store what each operation slot is doing
-
-        // fn to increment with carry on the ops map
-        fn inc_ops_map(ops_map: &mut Vec<u8>) -> bool {     // return true if we aren't finished yet
-            if ops_map.iter().all(|&o| o == OP_JOIN) {
-                return false;                               // all combinations exhausted
+            if nums.len() > MAX_NUMS + 1 {
+                return Err(AocError::in_line(y, format!("too many numbers after the colon (at most {}, as every combination of operators is tried)", MAX_NUMS)));
             }
-            let mut carry = true;  // carry in a value to increment the first slot
-            for i in 0..ops_map.len() {
-                ops_map[i] += carry as u8;              // increment if we have a carry
-                carry = ops_map[i] == OP_OVERFLOW;      // do we now have a carry
-                if carry {
-                    ops_map[i] = OP_ADD;                // reset this slot
+            Ok(nums)
+        }).collect()
+    }
+
+    fn part1(&self, data: &mut Self::Input) -> AocResult<String> {
+        info!("part 1 calculating...");
+
+        // evaluate the equation left to right, operators are + or *
+        // we want to know which lines could be valid, and sum those totals
+        let valid_data: Vec<&Vec<usize>> = data.iter().filter(|r| {
+            // n.b. the target result is stored in r[0]
+
+            trace!("line: {r:?}");
+
+            let num_ops = r.len() - 2;
+
+            // we will use a bitmap to store what operation we are performing. i.e. 0 for add, 1 for multiply.
+            // we will be done when we increment the bitmap and hit op_map_done
+            let op_map_done: u64 = 1 << (num_ops + 1);
+
+            let mut valid_line = false;
+
+            for op_map in 0..op_map_done {
+                // apply the ops specified in op_map to the numbers
+                // (the numbers never get smaller, so one too big to hold can't be the target)
+                let mut result = Some(r[1]);  // start with the leftmost number
+                for i in 2..r.len() {
+                    let op = (op_map >> (i-2)) & 0x01;  // find what operation to perform
+                    result = match op {
+                        0 => result.and_then(|n| n.checked_add(r[i])),
+                        1 => result.and_then(|n| n.checked_mul(r[i])),
+                        _ => panic!("invalid op"),
+                    }
                 }
-            }
-            if carry {
-                panic!("unexpected carry overflow");
-            }
-            true
-        }
-
-        let mut valid_line = false;
-        let mut done = false;
-
-        while !(done || valid_line) {
-            // now apply the ops specified in ops_map to the numbers
-            let mut result = r[1];
-            for i in 2..r.len() {       // for each number
-                let op = ops_map[i-2];
-                match op {              // perform the operation and store into the accumulator (result)
-                    0 => result += r[i],
-                    1 => result *= r[i],
-                    2 => {
-                        let a = result.to_string();
-                        let b = r[i].to_string();
-                        result = (a + &b).parse().expect("number");
-                    },
-                    _ => panic!("invalid op"),
+                // does our result match the target result?
+                if result == Some(r[0]) {
+                    valid_line = true;
                 }
             }
-            // does result match the target number?
-            if result == r[0] {
-                valid_line = true;
+            trace!("{valid_line}");
+            valid_line
+        }).collect();
+
+        let valid_sum1: usize = valid_data.iter().map(|r| r[0]).sum();
+        info!("part one sum: {valid_sum1}");
+        Ok(valid_sum1.to_string())
+    }
+
+    fn part2(&self, data: &mut Self::Input) -> AocResult<String> {
+        info!("part 2 calculating...");
+
+        let valid_data: Vec<&Vec<usize>> = data.iter().filter(|r| {
+            // target is in r[0]
+            trace!("line: {r:?}");
+
+            const OP_ADD: u8 = 0;
+            const _OP_MUL: u8 = 1;
+            const OP_JOIN: u8 = 2;
+            const OP_OVERFLOW: u8 = 3;
+
+            let num_ops = r.len() - 2;                          // number of operation slots
+            let mut ops_map: Vec<u8> = vec![OP_ADD; num_ops];   // where we store what each operation slot is doing
+
+            // fn to increment with carry on the ops map
+            fn inc_ops_map(ops_map: &mut Vec<u8>) -> bool {     // return true if we aren't finished yet
+                if ops_map.iter().all(|&o| o == OP_JOIN) {
+                    return false;                               // all combinations exhausted
+                }
+                let mut carry = true;  // carry in a value to increment the first slot
+                for i in 0..ops_map.len() {
+                    ops_map[i] += carry as u8;              // increment if we have a carry
+                    carry = ops_map[i] == OP_OVERFLOW;      // do we now have a carry
+                    if carry {
+                        ops_map[i] = OP_ADD;                // reset this slot
+                    }
+                }
+                if carry {
+                    panic!("unexpected carry overflow");
+                }
+                true
             }
-            done = !inc_ops_map(&mut ops_map);  // increment with carry on ops_map, and set the done flag if we've tried every combo
-        }
-        //println!("{valid_line}");
-        valid_line
-    }).collect();
 
-    let valid_sum2: usize = valid_data.iter().map(|r| r[0]).sum();
-    println!("part two sum: {valid_sum2}");
-
-    (valid_sum1.to_string(), valid_sum2.to_string())
-}
\ No newline at end of file
+            let mut valid_line = false;
+            let mut done = false;
+
+            while !(done || valid_line) {
+                // now apply the ops specified in ops_map to the numbers
+                // (None once the result is too big to hold, which can't be the target)
+                let mut result = Some(r[1]);
+                for i in 2..r.len() {       // for each number
+                    let op = ops_map[i-2];
+                    result = match (op, result) {   // perform the operation and store into the accumulator (result)
+                        (_, None) => None,
+                        (0, Some(n)) => n.checked_add(r[i]),
+                        (1, Some(n)) => n.checked_mul(r[i]),
+                        (2, Some(n)) => {
+                            let a = n.to_string();
+                            let b = r[i].to_string();
+                            (a + &b).parse().ok()
+                        },
+                        _ => panic!("invalid op"),
+                    }
+                }
+                // does result match the target number?
+                if result == Some(r[0]) {
+                    valid_line = true;
+                }
+                done = !inc_ops_map(&mut ops_map);  // increment with carry on ops_map, and set the done flag if we've tried every combo
+            }
+            trace!("{valid_line}");
+            valid_line
+        }).collect();
+
+        let valid_sum2: usize = valid_data.iter().map(|r| r[0]).sum();
+        info!("part two sum: {valid_sum2}");
+        Ok(valid_sum2.to_string())
+    }
+}
@@ -1,94 +1,106 @@
 use crate::grid::{*};
+use crate::sparse::SparseGrid;
+use crate::vector::Vector;
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 use itertools::Itertools;
 
-pub fn day08(input: &String) -> (String,String) {
-
-    // nodes are a-zA-Z0-9
-    // antinodes are created in two directions from each pair of matching nodes\
-    // how many antinodes are there (on the map)?
-
-    // read input into grid
-    let grid = Grid::from_str(input);
-    //println!("grid w {0} h {1}", grid.w, grid.h);
-
-    let mut antinodes = vec![];
-
-    // for each node possibility, find all nodes
-    for node in b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"[..].iter()  {
-        let positions = grid.find(*node);
-        if positions.len() > 0 {
-            // print!("'{0}' at ", *node as char);
-            // for p in &positions {
-            //     print!("{0} ", p.to_string());
-            // }
-            // println!();
-            // find antinodes
-            // i.e. for each pair of the set, find the two antinodes
-            for pair in positions.iter().combinations(2) {
-                //println!("combo {0} {1}", pair[0].to_string(), pair[1].to_string());
-                let d = pair[1].sub(pair[0]);
-                let an0 = pair[0].sub(&d);
-                let an1 = pair[1].add(&d);
-                //println!("antinodes at {0} {1}", an0.to_string(), an1.to_string());
-                antinodes.push(an0);
-                antinodes.push(an1);
-            }
-        }
+pub struct Day08;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day08);
+}
+
+// nodes are a-zA-Z0-9
+const NODES: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+impl Solver for Day08 {
+    type Input = Grid;
 
+    fn name(&self) -> &'static str { "day08" }
+    fn day(&self) -> usize { 8 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // read input into grid
+        Ok(Grid::from_str(input)?)
     }
 
-    // count how many unique ones that are on the map
-    antinodes.sort();
-    antinodes.dedup();
-    let antinodes = antinodes.iter().filter(|n| n.is_valid(&grid)).collect_vec();
-    println!("part one: unique antinodes: {}", antinodes.len());
-    let result1 = antinodes.len();
-
-    // part two
-    // antinodes also occur at pairs, and at every multiple of the spacing
-
-    let mut antinodes = vec![];
-
-    // for each node possibility, find all nodes
-    for node in b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"[..].iter()  {
-        let positions = grid.find(*node);
-        if positions.len() > 0 {
-            // print!("'{0}' at ", *node as char);
-            // for p in &positions {
-            //     print!("{0} ", p.to_string());
-            // }
-            // println!();
-            // find antinodes
-            // i.e. for each pair of the set, find the antinodes in both directions until off-map
-            for pair in positions.into_iter().combinations(2) {
-                //println!("combo {0} {1}", pair[0].to_string(), pair[1].to_string());
-                let d = pair[1].sub(&pair[0]);
-                antinodes.push(pair[0]);
-                antinodes.push(pair[1]);
-
-                // first direction
-                let mut an0 = pair[0].sub(&d);
-                while grid.has_xy(&an0) {
-                    antinodes.push(an0);
-                    an0 = an0.sub(&d);
-                }
+    fn part1(&self, grid: &mut Self::Input) -> AocResult<String> {
+        // antinodes are created in two directions from each pair of matching nodes\
+        // how many antinodes are there (on the map)?
+        trace!("grid w {0} h {1}", grid.w, grid.h);
+
+        // the antinodes can be off the map, so they go in a sparse grid
+        let mut antinodes: SparseGrid<u8> = SparseGrid::new();
 
-                // second direction
-                let mut an1 = pair[1].add(&d);
-                while grid.has_xy(&an1) {
-                    antinodes.push(an1);
-                    an1 = an1.add(&d);
+        // for each node possibility, find all nodes
+        for node in NODES.iter()  {
+            let positions = grid.find(*node);
+            if positions.len() > 0 {
+                trace!("'{0}' at {1}", *node as char, positions.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(" "));
+                // find antinodes
+                // i.e. for each pair of the set, find the two antinodes
+                for pair in positions.iter().combinations(2) {
+                    trace!("combo {0} {1}", pair[0].to_string(), pair[1].to_string());
+                    let d = pair[1].sub(pair[0]);
+                    let an0 = pair[0].sub(&d);
+                    let an1 = pair[1].add(&d);
+                    trace!("antinodes at {0} {1}", an0.to_string(), an1.to_string());
+                    antinodes.put(&an0, *node);
+                    antinodes.put(&an1, *node);
                 }
             }
+
+        }
+        if crate::log::enabled(crate::log::Level::Trace) {
+            let (g, origin) = antinodes.to_grid(b'.');
+            trace!("all antinodes, from {}:\n{}", origin.to_string(), g.to_string());
         }
 
+        // count how many unique ones that are on the map
+        let count = antinodes.count_within(&Vector::new(0, 0), &Vector::new(grid.w - 1, grid.h - 1));
+        info!("part one: unique antinodes: {}", count);
+        Ok(count.to_string())
     }
 
-    // count how many unique ones that are on the map
-    antinodes.sort();
-    antinodes.dedup();
-    println!("part two: unique antinodes: {}", antinodes.len());
-    let result2 = antinodes.len();
+    fn part2(&self, grid: &mut Self::Input) -> AocResult<String> {
+        // antinodes also occur at pairs, and at every multiple of the spacing
 
-    (result1.to_string(), result2.to_string())
-}
\ No newline at end of file
+        let mut antinodes: SparseGrid<u8> = SparseGrid::new();
+
+        // for each node possibility, find all nodes
+        for node in NODES.iter()  {
+            let positions = grid.find(*node);
+            if positions.len() > 0 {
+                trace!("'{0}' at {1}", *node as char, positions.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(" "));
+                // find antinodes
+                // i.e. for each pair of the set, find the antinodes in both directions until off-map
+                for pair in positions.into_iter().combinations(2) {
+                    trace!("combo {0} {1}", pair[0].to_string(), pair[1].to_string());
+                    let d = pair[1].sub(&pair[0]);
+                    antinodes.put(&pair[0], *node);
+                    antinodes.put(&pair[1], *node);
+
+                    // first direction
+                    let mut an0 = pair[0].sub(&d);
+                    while grid.has_xy(&an0) {
+                        antinodes.put(&an0, *node);
+                        an0 = an0.sub(&d);
+                    }
+
+                    // second direction
+                    let mut an1 = pair[1].add(&d);
+                    while grid.has_xy(&an1) {
+                        antinodes.put(&an1, *node);
+                        an1 = an1.add(&d);
+                    }
+                }
+            }
+
+        }
+
+        // count how many unique ones that are on the map (they all are)
+        info!("part two: unique antinodes: {}", antinodes.len());
+        Ok(antinodes.len().to_string())
+    }
+}
@@ -2,39 +2,61 @@ use crate::grid::{*};
 use itertools::Itertools;
 use crate::vector::{*};
 use crate::path::ALLMOVES;
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 use std::collections::{*};
 
-pub fn day10(input: &String) -> (String,String) {
-    // find trails from trailhead (0) to peak (9), incrementing one each time
-    // trailhead score is how many 9s are reachable
-    // sum of trailhead scores is the answer to part one
-    // part two: ratings: number of distinct trails (i.e. different paths that go from 0 to any 9)
+pub struct Day10;
 
-    // read input into grid
-    let grid = Grid::from_str(input);
-    println!("grid w {0} h {1}", grid.w, grid.h);
+pub fn register(r: &mut Registry) {
+    r.add(Day10);
+}
+
+// find trails from trailhead (0) to peak (9), incrementing one each time
+// trailhead score is how many 9s are reachable
+// sum of trailhead scores is the answer to part one
+// part two: ratings: number of distinct trails (i.e. different paths that go from 0 to any 9)
+
+impl Solver for Day10 {
+    type Input = Grid;
 
-    let mut scores: Vec<usize> = vec![];
-    let mut ratings: Vec<usize> = vec![];
+    fn name(&self) -> &'static str { "day10" }
+    fn day(&self) -> usize { 10 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // read input into grid
+        let grid = Grid::from_str(input)?;
+        debug!("grid w {0} h {1}", grid.w, grid.h);
+        Ok(grid)
+    }
 
-    // find trailheads
-    let trailheads = grid.find(b'0');
+    fn part1(&self, grid: &mut Self::Input) -> AocResult<String> {
+        // for each trailhead, the 9s reachable by climbing one each step
+        let scores: Vec<usize> = grid.find(b'0').iter().map(|th| {
+            grid.flood_fill(th, Connectivity::Four, |&a, &b| b == a + 1).iter().filter(|&xy| grid.get_unchecked(xy) == b'9').count()
+        }).collect();
 
-    // for each trailhead
-    for th in trailheads {
-        let mut nines: BTreeSet<Vector> = BTreeSet::new();
-        let mut unique_hikes: Vec<Vec<Vector>> = vec![];
-        path_walk(&grid, vec![th], &mut nines, &mut unique_hikes);
-        scores.push(nines.len());
-        ratings.push(unique_hikes.len());
+        trace!("scores: {0:?}", scores);
+        let th_score: usize = scores.iter().sum();
+        info!("part one scores sum: {th_score}");
+        Ok(th_score.to_string())
     }
 
-    //println!("scores: {0:?}", scores);
-    let th_score: usize = scores.iter().sum();
-    let th_ratings: usize = ratings.iter().sum();
-    println!("part one scores sum: {th_score}");
-    println!("part two ratings sum: {th_ratings}");
-    (th_score.to_string(), th_ratings.to_string())
+    fn part2(&self, grid: &mut Self::Input) -> AocResult<String> {
+        let mut ratings: Vec<usize> = vec![];
+
+        // for each trailhead
+        for th in grid.find(b'0') {
+            let mut nines: BTreeSet<Vector> = BTreeSet::new();
+            let mut unique_hikes: Vec<Vec<Vector>> = vec![];
+            path_walk(&grid, vec![th], &mut nines, &mut unique_hikes);
+            ratings.push(unique_hikes.len());
+        }
+
+        let th_ratings: usize = ratings.iter().sum();
+        info!("part two ratings sum: {th_ratings}");
+        Ok(th_ratings.to_string())
+    }
 }
 
 fn path_walk(grid: &Grid, path: Vec<Vector>, nines: &mut BTreeSet<Vector>, hike_paths: &mut Vec<Vec<Vector>> ) {
@@ -61,4 +83,4 @@ fn path_walk(grid: &Grid, path: Vec<Vector>, nines: &mut BTreeSet<Vector>, hike_
         npath.push(nxy);
         path_walk(grid, npath, nines, hike_paths);
     });
-}
\ No newline at end of file
+}
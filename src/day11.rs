@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
-use itertools::Itertools;
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
 struct Num(usize);
@@ -10,12 +11,15 @@ struct Node {
     children: Option<(Num, Option<Num>)>,
 }
 
-fn blink_node(src: &BTreeMap<Num,Node>, dest: &mut BTreeMap<Num,Node>, n: Num) {
+fn blink_node(src: &BTreeMap<Num,Node>, dest: &mut BTreeMap<Num,Node>, n: Num) -> AocResult<()> {
     let node = src.get(&n).unwrap();
 
     // calculate the children, if they don't exist
     if node.children.is_none() {
-        dest.get_mut(&n).unwrap().children = Some(calculate_blink(n));
+        let Some(children) = calculate_blink(n) else {
+            return Err(AocError::new(format!("stone {} grows too big to hold ({} * 2024)", n.0, n.0)));
+        };
+        dest.get_mut(&n).unwrap().children = Some(children);
     }
 
     // increment the children
@@ -38,119 +42,92 @@ fn blink_node(src: &BTreeMap<Num,Node>, dest: &mut BTreeMap<Num,Node>, n: Num) {
 
     // decrement ourselves
     dest.get_mut(&n).unwrap().count -= count;
+    Ok(())
 }
 
 fn calculate_count(map: &BTreeMap<Num, Node>) -> usize {
     map.iter().map(|(_, n)| n.count).sum::<usize>()
 }
 
-pub fn day11(input: &String) -> (String,String) {
-    // read in numbers
-    let input: Vec<usize> = input.trim_end().split_whitespace().map(|s| s.parse::<usize>().expect("number")).collect_vec();
-
+fn blink_count(input: &Vec<usize>, blinks: usize) -> AocResult<usize> {
     let mut map: BTreeMap<Num, Node> = BTreeMap::new();
 
-    for s in input {
+    for &s in input {
         map.insert(Num(s), Node { count: 1, children: None });
     }
 
-    let mut part1_count: usize = 0;
-    let mut part2_count: usize = 0;
-    let t0 = crate::time::get_time_ms();
-    let mut blinks : usize;
-
-    for i in 0..75 {
+    for _i in 0..blinks {
         let mut dest = map.clone();
         for &k in map.keys() {
-            blink_node(&map, &mut dest, k);
+            blink_node(&map, &mut dest, k)?;
         }
         map = dest;
-        blinks = i + 1;
 
-        //println!("depth {blinks}, count {0}", calculate_count(&map));
-        // print!("nodes: ");
-        // map.iter().for_each(|(k,n)| {
-        //     print!("{0}:{1} ", k.0, n.to_string());
-        // });
-        // println!();
+        trace!("depth {0}, count {1}", _i + 1, calculate_count(&map));
+        trace!("nodes: {}", map.iter().map(|(k,n)| format!("{0}:{1}", k.0, n.count)).collect::<Vec<String>>().join(" "));
+    }
 
-        if blinks == 25 {
-            part1_count = calculate_count(&map);
-        }
-        if blinks == 75 {
-            part2_count = calculate_count(&map);
+    Ok(calculate_count(&map))
+}
+
+pub struct Day11;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day11);
+}
+
+impl Solver for Day11 {
+    type Input = Vec<usize>;
+
+    fn name(&self) -> &'static str { "day11" }
+    fn day(&self) -> usize { 11 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        // read in numbers
+        let mut stones: Vec<usize> = vec![];
+        for (y,line) in input.lines().enumerate() {
+            for (x,s) in tokens(line) {
+                stones.push(parse_at(s, y, x)?);
+            }
         }
+        Ok(stones)
     }
 
-    println!("time: {0:4.3}s", (crate::time::get_time_ms() - t0)/1000_f64);
-    println!("part one: {part1_count}");
-    println!("part two: {part2_count}");
-    (part1_count.to_string(), part2_count.to_string())
+    fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+        let part1_count = blink_count(input, 25)?;
+        info!("part one: {part1_count}");
+        Ok(part1_count.to_string())
+    }
+
+    fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+        let part2_count = blink_count(input, 75)?;
+        info!("part two: {part2_count}");
+        Ok(part2_count.to_string())
+    }
 }
 
 fn count_digits(n: usize) -> (usize, bool) {
     // return true if number of digits is even
-    if n < 10 {
-        return (1, false);
-    }
-    if n < 100 {
-        return (2, true);
-    }
-    if n < 1000 {
-        return (3, false);
-    }
-    if n < 10000 {
-        return (4, true);
-    }
-    if n < 100000 {
-        return (5, false);
-    }
-    if n < 1000000 {
-        return (6, true);
-    }
-    if n < 10000000 {
-        return (7, false);
-    }
-    if n < 100000000 {
-        return (8, true);
-    }
-    if n < 1000000000 {
-        return (9, false);
-    }
-    if n < 10000000000 {
-        return (10, true);
-    }
-    if n < 100000000000 {
-        return (11, false);
-    }
-    if n < 1000000000000 {
-        return (12, true);
-    }
-    panic!("count_digits input is too high!");
+    let c = n.checked_ilog10().unwrap_or(0) as usize + 1;
+    (c, c % 2 == 0)
 }
 
-fn calculate_blink(n: Num) -> (Num, Option<Num>) {
+// the stones after a blink, or None if the stone would be too big to hold
+fn calculate_blink(n: Num) -> Option<(Num, Option<Num>)> {
     if n.0 == 0 {
-        return (Num(1), None);
+        return Some((Num(1), None));
     }
     let c = count_digits(n.0);
     if c.1 {
         let (lh,rh) = split_num(n.0, c.0);
-        return (Num(lh),Some(Num(rh)));
+        return Some((Num(lh),Some(Num(rh))));
     }
-    return (Num(n.0 * 2024), None);
+    Some((Num(n.0.checked_mul(2024)?), None))
 }
 
 fn split_num(n: usize, c: usize) -> (usize, usize) {
     // split n into two, if number of digits is even
     // c is count of digits
-    match c {
-        2 =>  (n/10,      n%10),
-        4 =>  (n/100,     n%100),
-        6 =>  (n/1000,    n%1000),
-        8 =>  (n/10000,   n%10000),
-        10 => (n/100000,  n%100000),
-        12 => (n/1000000, n%1000000),
-        _ => panic!("expect even number of digits <= 12"),
-    }
+    let d = 10_usize.pow(c as u32 / 2);
+    (n / d, n % d)
 }
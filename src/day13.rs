@@ -3,8 +3,11 @@ use itertools::Itertools;
 //use crate::grid::{*};
 //use crate::vector::{*};
 //use crate::vector::Vector;
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
-struct Machine {
+#[derive(Clone,Copy)]
+pub struct Machine {
     a: (usize, usize),
     b: (usize, usize),
     t: (usize, usize),
@@ -17,95 +20,132 @@ struct Combo {
     b_count: usize,
     cost: usize,
 }
-pub fn day13(input: &String) -> (String,String) {
-    let mut total_cost: usize = 0;
-    let input = input.lines().collect_vec();
-    let machine_count = (input.len()+1)/ 4;
-    println!("machine_count: {}", machine_count);
-    let mut machines: Vec<Machine> = Vec::with_capacity(machine_count);
-
-    // read in all the data
-    for m in 0..machine_count {
-        let lines = &input[(m * 4)..(m * 4 + 3)];
-        let ax: usize = lines[0][12..14].parse::<usize>().unwrap();
-        let ay: usize = lines[0][18..20].parse::<usize>().unwrap();
-        let bx: usize = lines[1][12..14].parse::<usize>().unwrap();
-        let by: usize = lines[1][18..20].parse::<usize>().unwrap();
-        let re = regex::Regex::new(r"X=(\d+), Y=(\d+)").expect("valid regex");
-        let caps: [&str; 2] = re.captures(lines[2]).expect("captures").extract().1;
-        let t_x = caps[0].parse::<usize>().unwrap();
-        let t_y = caps[1].parse::<usize>().unwrap();
-        machines.push(Machine { a: (ax, ay), b: (bx, by), t: (t_x, t_y) });
-    }
 
-    for (_i, m) in machines.iter().enumerate() {
-        // find minimum number of button presses that will get close to target
-        // println!("machine {}: ", i+1);
-        // println!("  {:?} {:?} {:?}", m.a, m.b, m.t);
-        let mut combos: Vec<Combo> = vec![];
-        for na in 1..=100 {
-            for nb in 1..=100 {
-                if (m.a.0 * na + m.b.0 * nb) == m.t.0 && (m.a.1 * na + m.b.1 * nb) == m.t.1 {
-                    combos.push(Combo { a_count: na, b_count: nb, cost: 3 * na + nb });
-                }
+pub struct Day13;
+
+pub fn register(r: &mut Registry) {
+    r.add(Day13);
+}
+
+impl Solver for Day13 {
+    type Input = Vec<Machine>;
+
+    fn name(&self) -> &'static str { "day13" }
+    fn day(&self) -> usize { 13 }
+
+    fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+        let input = input.lines().collect_vec();
+        let mut machines: Vec<Machine> = Vec::with_capacity((input.len() + 1) / 4);
+
+        // each machine is three lines, followed by a blank line
+        let re_a = regex::Regex::new(r"^Button A: X\+(\d+), Y\+(\d+)$").expect("valid regex");
+        let re_b = regex::Regex::new(r"^Button B: X\+(\d+), Y\+(\d+)$").expect("valid regex");
+        let re_t = regex::Regex::new(r"^Prize: X=(\d+), Y=(\d+)$").expect("valid regex");
+        let read_line = |re: &regex::Regex, y: usize, what: &str| -> AocResult<(usize, usize)> {
+            let Some(line) = input.get(y) else {
+                return Err(AocError::in_line(y, format!("unexpected end of input, expected '{}'", what)));
+            };
+            let Some(caps) = re.captures(line.trim_end()) else {
+                return Err(AocError::in_line(y, format!("expected '{}'", what)));
+            };
+            let (x0, x1) = (caps.get(1).unwrap(), caps.get(2).unwrap());
+            Ok((parse_at(x0.as_str(), y, x0.start())?, parse_at(x1.as_str(), y, x1.start())?))
+        };
+
+        // read in all the data, until the input runs out (any line left over must be the start of a whole machine)
+        let mut y = 0;
+        while y < input.len() {
+            if input[y].trim().is_empty() {
+                y += 1;
+                continue;
+            }
+            let a = read_line(&re_a, y, "Button A: X+n, Y+n")?;
+            let b = read_line(&re_b, y + 1, "Button B: X+n, Y+n")?;
+            let t = read_line(&re_t, y + 2, "Prize: X=n, Y=n")?;
+            if input.get(y + 3).is_some_and(|l| !l.trim().is_empty()) {
+                return Err(AocError::in_line(y + 3, "expected a blank line between machines"));
             }
+            machines.push(Machine { a, b, t });
+            y += 4;
         }
-        //println!("  {} combos found", combos.len());
-        if combos.len() > 0 {
-            combos.sort_by(|a, b| a.cost.cmp(&b.cost));
-            //println!("  cheapest is a: {}, b: {}, cost: {}", combos[0].a_count, combos[0].b_count, combos[0].cost);
-            total_cost += combos[0].cost;
+        debug!("machine_count: {}", machines.len());
+        if machines.is_empty() {
+            return Err(AocError::new("no machines found"));
         }
+        Ok(machines)
     }
 
-    //println!("part one total cost: {total_cost}");
+    fn part1(&self, machines: &mut Self::Input) -> AocResult<String> {
+        let mut total_cost: usize = 0;
 
-    // part two
-    let mut total_cost_two: usize = 0;
-    for m in machines.iter_mut() {
-        m.t = (m.t.0 + 10000000000000, m.t.1 + 10000000000000);
+        for (i, m) in machines.iter().enumerate() {
+            // find minimum number of button presses that will get close to target
+            trace!("machine {}:", i+1);
+            trace!("  {:?} {:?} {:?}", m.a, m.b, m.t);
+            let mut combos: Vec<Combo> = vec![];
+            for na in 1..=100 {
+                for nb in 1..=100 {
+                    if (m.a.0 * na + m.b.0 * nb) == m.t.0 && (m.a.1 * na + m.b.1 * nb) == m.t.1 {
+                        combos.push(Combo { a_count: na, b_count: nb, cost: 3 * na + nb });
+                    }
+                }
+            }
+            trace!("  {} combos found", combos.len());
+            if combos.len() > 0 {
+                combos.sort_by(|a, b| a.cost.cmp(&b.cost));
+                trace!("  cheapest is a: {}, b: {}, cost: {}", combos[0].a_count, combos[0].b_count, combos[0].cost);
+                total_cost += combos[0].cost;
+            }
+        }
+
+        info!("part one total cost: {total_cost}");
+        Ok(total_cost.to_string())
     }
 
-    for (_i,m) in machines.iter().enumerate() {
-        // can't brute force for part two :)
-        // consider the direction each button makes as a line.
-        // there are only two possibilities for the lines: line a from ("no result".to_string(), "no result".to_string()), and line b via (tx,ty) (and vice-versa).
-        // the solution to the problem will be the same regardless, as the lines will have the same length to reach
-        // the intersection point.
-        //
-        // we'll calculate the intersection point, then check we can get there in an integer multiple of the button move.
-        // we are using floats here, but could also use e.g. the Fraction crate
-        //
-        // #neat_things_seen: simpler algebra, no need to consider the line formula!
-
-        //println!("machine {}: ", i+1);
-        //println!("  {:?} {:?} {:?}", m.a, m.b, m.t);
-
-        let a = (m.a.0 as f64, m.a.1 as f64);
-        let b = (m.b.0 as f64, m.b.1 as f64);
-        let t = (m.t.0 as f64, m.t.1 as f64);
-
-        let ma = a.1 / a.0;                 // gradient of line a
-        let mb = b.1 / b.0;                 // gradient of line b
-        let cb = t.1 - (b.1/b.0) * t.0;     // y-intercept of line b
-        let _xi = (ma - mb) / cb;            // x of intercept point
-        let yi = ma  * cb / (ma - mb);      // y of intercept point
-        let ac = ((yi/a.1).round()) as usize;     // how many times to press button a
-        let bc = ((t.1-yi)/b.1).round() as usize; // how many times to press button b
-
-        // println!("  xi: {xi}, yi: {yi}, cb: {cb}, ac: {ac}, bc: {bc}");
-
-        // test the answer to see if it is legit in integer terms
-        if (m.a.0 * ac + m.b.0 * bc) == m.t.0 && (m.a.1 * ac + m.b.1 * bc) == m.t.1 {
-            let cost = 3 * ac + bc;
-            //println!("  solution is a: {}, b: {}, cost: {}", ac, bc, cost);
-            total_cost_two += cost;
-        } else {
-            //println!("  no solution found");
+    fn part2(&self, machines: &mut Self::Input) -> AocResult<String> {
+        let mut total_cost_two: usize = 0;
+        let machines = machines.iter().map(|m| Machine { t: (m.t.0 + 10000000000000, m.t.1 + 10000000000000), ..*m }).collect_vec();
+
+        for (i,m) in machines.iter().enumerate() {
+            // can't brute force for part two :)
+            // consider the direction each button makes as a line.
+            // there are only two possibilities for the lines: line a from ("no result".to_string(), "no result".to_string()), and line b via (tx,ty) (and vice-versa).
+            // the solution to the problem will be the same regardless, as the lines will have the same length to reach
+            // the intersection point.
+            //
+            // we'll calculate the intersection point, then check we can get there in an integer multiple of the button move.
+            // we are using floats here, but could also use e.g. the Fraction crate
+            //
+            // #neat_things_seen: simpler algebra, no need to consider the line formula!
+
+            trace!("machine {}:", i+1);
+            trace!("  {:?} {:?} {:?}", m.a, m.b, m.t);
+
+            let a = (m.a.0 as f64, m.a.1 as f64);
+            let b = (m.b.0 as f64, m.b.1 as f64);
+            let t = (m.t.0 as f64, m.t.1 as f64);
+
+            let ma = a.1 / a.0;                 // gradient of line a
+            let mb = b.1 / b.0;                 // gradient of line b
+            let cb = t.1 - (b.1/b.0) * t.0;     // y-intercept of line b
+            let xi = (ma - mb) / cb;             // x of intercept point
+            let yi = ma  * cb / (ma - mb);      // y of intercept point
+            let ac = ((yi/a.1).round()) as usize;     // how many times to press button a
+            let bc = ((t.1-yi)/b.1).round() as usize; // how many times to press button b
+
+            trace!("  xi: {xi}, yi: {yi}, cb: {cb}, ac: {ac}, bc: {bc}");
+
+            // test the answer to see if it is legit in integer terms
+            if (m.a.0 * ac + m.b.0 * bc) == m.t.0 && (m.a.1 * ac + m.b.1 * bc) == m.t.1 {
+                let cost = 3 * ac + bc;
+                trace!("  solution is a: {}, b: {}, cost: {}", ac, bc, cost);
+                total_cost_two += cost;
+            } else {
+                trace!("  no solution found");
+            }
         }
-    }
 
-    println!("part one total cost: {total_cost}");
-    println!("part two total cost: {total_cost_two}");
-    (total_cost.to_string(), total_cost_two.to_string())
-}
\ No newline at end of file
+        info!("part two total cost: {total_cost_two}");
+        Ok(total_cost_two.to_string())
+    }
+}
@@ -1,95 +1,163 @@
 use itertools::Itertools;
 //use std::collections::{*};
-//use crate::vector::{*};
-
+use crate::vector::{Vector, Vector2};
 use crate::grid::{*};
+use crate::sparse::SparseGrid;
+use crate::solver::{Registry, Solver, Param, Params};
+use crate::error::{*};
+use crate::image::{self, Image, Frames, Palette};
 
 #[derive(Debug,Clone,Copy)]
 struct Robot {
-	p: (isize, isize),
-	v: (isize, isize),
+	p: Vector,
+	v: Vector,
 }
 
-pub fn day14(input: &String) -> (String, String) {
-	// read in input to get robot position and velocities
-	// NOTE: we've modified input to include the grid size on the first line
-	let re = regex::Regex::new(r"(-?\d+)").expect("valid regex");
-	let caps: Vec<isize> = re.find_iter(input).map(|m| m.as_str().parse::<isize>().unwrap()).collect_vec();
-	let (w,h) = (caps[0], caps[1]);
-	//println!("w: {w}, h: {h}");
-
-	let robot_desc = &caps[2..caps.len()];
-	let mut robots: Vec<Robot> = vec![];
-	let robot_count = robot_desc.len() / 4;
-	println!("robot count: {}", robot_count);
-
-	for i in 0..robot_count {
-		let [px, py, mut vx, mut vy] = robot_desc[i*4..(i+1)*4] else { panic!("invalid robot desc") };
-		if vx < 0 {			// keep them positive
-			vx = w + vx;
-		}
-		if vy < 0 {			// keep them positive
-			vy = h + vy;
-		}
-		robots.push(Robot{p: (px,py), v: (vx,vy)});
-		//println!("robot: {:?} {:?}", robots[i].p, robots[i].v);
-	}
+pub struct Input {
+	w: i32,
+	h: i32,
+	robots: Vec<Robot>,
+	frames: usize,
+}
 
-	let mut quads: [usize; 4] = [0; 4];
-	let x_midpoint: isize = w / 2;
-	let y_midpoint: isize = h / 2;
-
-	for r in &robots {
-		// after 100 seconds
-		let px = (r.p.0 + r.v.0 * 100) % w;
-		let py = (r.p.1 + r.v.1 * 100) % h;
-		if px < x_midpoint && py < y_midpoint {
-			quads[0] += 1;
-		} else if px > x_midpoint && py < y_midpoint {
-			quads[1] += 1;
-		} else if px < x_midpoint && py > y_midpoint {
-			quads[2] += 1;
-		} else if px > x_midpoint && py > y_midpoint {
-			quads[3] += 1;
-		} else {
-			// on midpoint, not counted
-		}
-	}
+pub struct Day14;
 
-	let p1_result: usize = quads.iter().product();
-	println!("part 1 result: {}", p1_result);
+const PARAMS: &[Param] = &[
+	Param { name: "w", default: "101", help: "grid width (the example is 11)" },
+	Param { name: "h", default: "103", help: "grid height (the example is 7)" },
+	Param { name: "frames", default: "0", help: "with --image-dir, also save the first N seconds of part 2 as images" },
+];
 
-	// part two
-	// now we have to actually look at the grid !!!
-	// the pattern for the tree was originally discovered by filtering out times where the
-	// middle vertical third of the grid had a much higher density of robots than expected
-	// i.e. > 2/3, and scrolling through a few pages of output. the discovered value from
-	// that method was too high, but it showed us what to look for!
+pub fn register(r: &mut Registry) {
+	r.add(Day14);
+}
+
+impl Solver for Day14 {
+	type Input = Input;
+
+	fn name(&self) -> &'static str { "day14" }
+	fn day(&self) -> usize { 14 }
+	fn params(&self) -> &'static [Param] { PARAMS }
 
-	println!("part 2 calculating...");
+	fn parse(&self, input: &str, params: &Params) -> AocResult<Self::Input> {
+		// read in input to get robot position and velocities
+		// the grid size comes from the w and h params, or from a first line of s=w,h (as we used to edit into the input)
+		let re = regex::Regex::new(r"(-?\d+)").expect("valid regex");
+		let mut caps: Vec<(usize, i32)> = regex_nums(&re, input)?;
+		let (mut w, mut h): (i32, i32) = (params.get("w")?, params.get("h")?);
+		if input.starts_with("s=") && caps.len() >= 2 {
+			(w, h) = (caps[0].1, caps[1].1);
+			caps.drain(0..2);
+		}
+		if caps.len() % 4 != 0 {
+			return Err(AocError::at_offset(input, caps[caps.len() - 1].0, "expected four numbers per robot (p=x,y v=x,y)"));
+		}
+		if w <= 0 || h <= 0 {
+			return Err(AocError::new("grid size must be positive"));
+		}
+		let caps = caps.into_iter().map(|(_,n)| n).collect_vec();
+		trace!("w: {w}, h: {h}");
 
-	let mut p2_result = 0_usize;
+		let robot_desc = &caps[..];
+		let mut robots: Vec<Robot> = vec![];
+		let robot_count = robot_desc.len() / 4;
+		debug!("robot count: {}", robot_count);
 
-	for t in 1..10000 {
-		let mut grid = Grid::new_with(w as i32, h as i32, b'.');
-		let mut moved_robots = robots.clone();
 		for i in 0..robot_count {
-			let r = &robots[i];
-			moved_robots[i].p.0 = (r.p.0 + r.v.0 * t) % w;
-			moved_robots[i].p.1 = (r.p.1 + r.v.1 * t) % h;
-			grid.put_unchecked_t(moved_robots[i].p, b'#');
+			let [px, py, mut vx, mut vy] = robot_desc[i*4..(i+1)*4] else { unreachable!() };
+			if vx < 0 {			// keep them positive
+				vx = w + vx;
+			}
+			if vy < 0 {			// keep them positive
+				vy = h + vy;
+			}
+			robots.push(Robot{p: Vector::new(px,py), v: Vector::new(vx,vy)});
+			trace!("robot: {:?} {:?}", robots[i].p, robots[i].v);
 		}
-		let s = grid.to_string();
-		if s[0..s.len()/2].contains("##########################") {
-			println!("part 2 result: {}", t);
-			//println!("{}", grid.to_string());
-			p2_result = t as usize;
-			break;
+
+		Ok(Input { w, h, robots, frames: params.get("frames")? })
+	}
+
+	fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+		let (w, h) = (input.w, input.h);
+		let mut quads: [usize; 4] = [0; 4];
+		let x_midpoint: i32 = w / 2;
+		let y_midpoint: i32 = h / 2;
+
+		// the number of robots at each position after 100 seconds, wrapping around the edges
+		let mut robots: SparseGrid<usize> = SparseGrid::wrapping(w, h);
+		for r in &input.robots {
+			*robots.get_or_insert(&r.p.add(&r.v.mul(100)), 0) += 1;
 		}
-		if t % 1000 == 0 {
-			//println!("--- t={} ---", t);
+
+		for (Vector2(px, py), &n) in robots.iter() {
+			if px < x_midpoint && py < y_midpoint {
+				quads[0] += n;
+			} else if px > x_midpoint && py < y_midpoint {
+				quads[1] += n;
+			} else if px < x_midpoint && py > y_midpoint {
+				quads[2] += n;
+			} else if px > x_midpoint && py > y_midpoint {
+				quads[3] += n;
+			} else {
+				// on midpoint, not counted
+			}
 		}
+
+		let p1_result: usize = quads.iter().product();
+		info!("part 1 result: {}", p1_result);
+		Ok(p1_result.to_string())
 	}
 
-	(p1_result.to_string(), p2_result.to_string())
-}
\ No newline at end of file
+	fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+		// now we have to actually look at the grid !!!
+		// the pattern for the tree was originally discovered by filtering out times where the
+		// middle vertical third of the grid had a much higher density of robots than expected
+		// i.e. > 2/3, and scrolling through a few pages of output. the discovered value from
+		// that method was too high, but it showed us what to look for!
+
+		info!("part 2 calculating...");
+
+		let (w, h) = (input.w, input.h);
+		let robots = &input.robots;
+		let robot_count = robots.len();
+		let mut p2_result = 0_usize;
+		let palette = Palette::default().with(b"#", image::GREEN);
+		let torus: SparseGrid<u8> = SparseGrid::wrapping(w, h);
+		let mut frames = Frames::new("day14-frame");
+		let mut saving_frames = input.frames > 0 && image::enabled();
+
+		for t in 1..10000 {
+			let mut grid = Grid::new_with(w, h, b'.');
+			let mut moved_robots = robots.clone();
+			for i in 0..robot_count {
+				let r = &robots[i];
+				moved_robots[i].p = torus.wrap(&r.p.add(&r.v.mul(t)));
+				grid.put_unchecked(&moved_robots[i].p, b'#');
+			}
+			if saving_frames {
+				// the answer doesn't depend on the images, so a failure is only logged (once)
+				if let Err(e) = frames.save(&Image::from_grid(&grid, &palette).with_scale(4)) {
+					info!("{}", e);
+					saving_frames = false;
+				}
+				saving_frames &= frames.count() < input.frames;
+			}
+			let s = grid.to_string();
+			if s[0..s.len()/2].contains("##########################") {
+				info!("part 2 result: {}", t);
+				trace!("{}", grid.to_string());
+				if let Err(e) = image::save("day14-tree", &Image::from_grid(&grid, &palette).with_scale(4)) {
+					info!("{}", e);
+				}
+				p2_result = t as usize;
+				break;
+			}
+			if t % 1000 == 0 {
+				trace!("--- t={} ---", t);
+			}
+		}
+
+		Ok(p2_result.to_string())
+	}
+}
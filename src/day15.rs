@@ -4,142 +4,206 @@ use itertools::Itertools;
 use crate::grid::{*};
 use crate::vector::{*};
 use crate::path::{Move};
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
+use crate::render::{self, Animation, Palette, Picture};
+use crossterm::style::Color;
 
-pub fn day15(input: &String) -> (String, String) {
-	let line = &input[0..input.find(&['\n', '\r']).unwrap()];
-	let w = line.len();
-	let mut data: Vec<String> = vec![];
-	let mut lines = input.lines();
+pub struct Input {
+	w: usize,
+	h: usize,
+	bdata: Vec<u8>,
+	moves: Vec<Move>,
+}
 
-	// read in level data
-	loop {
-		let line = lines.next();
-		if line.unwrap().len() == 0 {
-			break;
+pub struct Day15;
+
+// the warehouse, with the robot
+fn picture(grid: &Grid, robot: Vector) -> Picture {
+	let mut pic = Picture::from_grid(grid, &Palette::default());
+	pic.point(robot, '@', Color::Green);
+	pic
+}
+
+pub fn register(r: &mut Registry) {
+	r.add(Day15);
+}
+
+impl Solver for Day15 {
+	type Input = Input;
+
+	fn name(&self) -> &'static str { "day15" }
+	fn day(&self) -> usize { 15 }
+
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		let mut lines = input.lines().enumerate();
+		let w = input.lines().next().map(|l| l.len()).unwrap_or(0);
+		let mut data: Vec<String> = vec![];
+		let mut robots = 0;
+
+		// read in level data
+		loop {
+			let Some((y, line)) = lines.next() else {
+				return Err(AocError::new("expected a blank line between the map and the moves"));
+			};
+			if line.len() == 0 {
+				break;
+			}
+			if line.len() != w {
+				return Err(AocError::in_line(y, format!("unexpected line width {}, expected {}", line.len(), w)));
+			}
+			for (x, c) in line.char_indices() {
+				match c {
+					'#' | '.' | 'O' => {},
+					'@' => {
+						robots += 1;
+						if robots > 1 {
+							return Err(AocError::at(y, x, "more than one robot found"));
+						}
+					},
+					_ => return Err(AocError::at(y, x, format!("unexpected character '{}' in map", c))),
+				}
+			}
+			data.push(line.to_string());
 		}
-		data.push(line.unwrap().to_string());
-	}
-	let bdata: Vec<u8> = data.iter().map(|s| s.bytes().collect_vec()).flatten().collect_vec();
-	let mut grid = Grid { w: w as i32, h: data.len() as i32, data: bdata.clone() };
+		if robots == 0 {
+			return Err(AocError::new("robot not found in map"));
+		}
+		let bdata: Vec<u8> = data.iter().map(|s| s.bytes().collect_vec()).flatten().collect_vec();
 
-	// read in movement data
-	let mut movements: Vec<String> = vec![];
-	loop {
-		let line = lines.next();
-		if line.is_none() {
-			break;
+		// read in movement data
+		let mut moves: Vec<Move> = vec![];
+		for (y, line) in lines {
+			for (x, c) in line.char_indices() {
+				match c {
+					'^' | '>' | 'v' | '<' => moves.push(Move::from_char_unchecked(c)),
+					_ => return Err(AocError::at(y, x, format!("unexpected move '{}'", c))),
+				}
+			}
 		}
-		movements.push(line.unwrap().to_string());
-	}
 
-	let mut moves_m: Vec<Vec<char>> = vec![];
-	for s in movements.iter() {
-		moves_m.push(s.chars().collect_vec());
+		debug!("robot moves: {}", moves.len());
+
+		Ok(Input { w, h: data.len(), bdata, moves })
 	}
-	let moves: Vec<Move> = moves_m.iter().flatten().filter(|&&c| c=='^' || c=='>' || c=='v' || c=='<').map(|&c| Move::from_char_unchecked(c)).collect_vec();
 
-	println!("robot moves: {}", moves.len());
-	println!("grid w: {}, h: {}", grid.w, grid.h);
-	// println!("initial position:\n{}", grid.to_string());
-	println!("part 1 calculating...");
+	fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+		let w = input.w;
+		let moves = &input.moves;
+		let mut grid = Grid { w: w as i32, h: input.h as i32, data: input.bdata.clone() };
+		debug!("grid w: {}, h: {}", grid.w, grid.h);
+		trace!("initial position:\n{}", grid.to_string());
+		info!("part 1 calculating...");
 
-	// find robot
-	let mut robot_xy = grid.find(b'@')[0];
+		// find robot
+		let mut robot_xy = grid.find(b'@')[0];
 
-	// remove from grid for easier work
-	grid.put(&robot_xy, b'.');
+		// remove from grid for easier work
+		grid.put(&robot_xy, b'.');
 
-	// make move
-	for &m in moves.iter() {
-		let able = push_boxes(&mut grid, &robot_xy, m);
-		if able {
-			// move robot
-			robot_xy = robot_xy.add(&m.to_vector());
-			//print!("{} ok. ", m.to_string());
-		} else {
-			//print!("{} failed. ", m.to_string());
+		// make move
+		let mut anim = Animation::new();
+		for (i,&m) in moves.iter().enumerate() {
+			let able = push_boxes(&mut grid, &robot_xy, m);
+			if able {
+				// move robot
+				robot_xy = robot_xy.add(&m.to_vector());
+				trace!("{} ok.", m.to_string());
+			} else {
+				trace!("{} failed.", m.to_string());
+			}
+			if let Some(a) = anim.as_mut() {
+				a.frame(&picture(&grid, robot_xy), &format!("after {} moves ({}):", i + 1, m.to_string()));
+			}
 		}
-	}
 
-	// println!("\nfinal position:\n{}", grid.to_string());
-	// calculate GPS score -- sum of each box's (100*by+bx)
-	let score: i32 = grid.find(b'O').iter().map(|v| v.0 + v.1 * 100).sum();
-	println!("part one score: {}", score);
+		trace!("\nfinal position:\n{}", grid.to_string());
+		if render::enabled() {
+			picture(&grid, robot_xy).draw("final position:");
+		}
+		// calculate GPS score -- sum of each box's (100*by+bx)
+		let score: i32 = grid.find(b'O').iter().map(|v| v.0 + v.1 * 100).sum();
+		info!("part one score: {}", score);
+		Ok(score.to_string())
+	}
 
-	println!("part 2 calculating...");
+	fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+		info!("part 2 calculating...");
 
-	// part two
-	// ddoouubbllee  wwiiddtthh
-	// read in level data
-	let mut ndata: Vec<u8> = vec![];
-	for b in bdata.iter() {
-		let dw = match b {
-			b'.' => b"..",
-			b'O' => b"[]",
-			b'@' => b"@.",
-			b'#' => b"##",
-			_ => panic!("unexpected input byte"),
-		};
-		ndata.push(dw[0]);
-		ndata.push(dw[1]);
-	}
+		// ddoouubbllee  wwiiddtthh
+		let w = input.w;
+		let moves = &input.moves;
 
-	let mut grid = Grid { w: w as i32 * 2, h: (ndata.len() / (w * 2)) as i32, data: ndata };
-	println!("grid w: {}, h: {}", grid.w, grid.h);
+		// each cell becomes two across: a box O becomes [], and the robot @ becomes @.
+		let grid = Grid { w: w as i32, h: input.h as i32, data: input.bdata.clone() };
+		let mut grid = grid.scale_with(2, 1, |&b, d| match (b, d.0) {
+			(b'O', 0) => b'[',
+			(b'O', _) => b']',
+			(b'@', 1) => b'.',
+			(b, _) => b,
+		});
+		debug!("grid w: {}, h: {}", grid.w, grid.h);
 
-	// find robot
-	let mut robot_xy = grid.find(b'@')[0];
+		// find robot
+		let mut robot_xy = grid.find(b'@')[0];
 
-	// remove from grid for easier work
-	grid.put(&robot_xy, b'.');
+		// remove from grid for easier work
+		grid.put(&robot_xy, b'.');
 
-	// make move
-	for (_i,&m) in moves.iter().enumerate() {
-		//println!("\nafter {i} moves:\n{}", grid.to_string_with_pt(&robot_xy));
-		let nxy = robot_xy.add_dir(&m);
-		let nobj = grid.get(&nxy);
-		if nobj.is_none() || nobj.unwrap() == b'#' {
-			//print!("{} failed. ", m.to_string());
-			continue;
-		}
-		let nobj = nobj.unwrap();
-		if nobj == b'[' || nobj == b']' {
-			let moving_boxes = box_tree_is_pushable(&mut grid, &nxy, m);
-			if let Some(mb) = moving_boxes {
-				// find new locations
-				let nmb = mb.iter().map(|v| v.add_dir(&m)).collect_vec();
-				// erase old locations
-				mb.iter().for_each(|&v| {
-					grid.put(&v, b'.');
-					grid.put(&v.add(&Vector(1, 0)), b'.');
-				});
-				// put boxes in new locations
-				nmb.iter().for_each(|&v| {
-					grid.put(&v, b'[');
-					grid.put(&v.add(&Vector(1, 0)), b']');
-				});
-				// move robot
-				robot_xy = nxy;
-				//print!("{} ok. ", m.to_string());
+		// make move
+		let mut anim = Animation::new();
+		for (i,&m) in moves.iter().enumerate() {
+			trace!("\nafter {i} moves:\n{}", grid.to_string_with_pt(&robot_xy));
+			if let Some(a) = anim.as_mut() {
+				a.frame(&picture(&grid, robot_xy), &format!("after {} moves (next {}):", i, m.to_string()));
+			}
+			let nxy = robot_xy.add_dir(&m);
+			let nobj = grid.get(&nxy);
+			if nobj.is_none() || nobj.unwrap() == b'#' {
+				trace!("{} failed.", m.to_string());
 				continue;
 			}
-			//print!("{} failed. ", m.to_string());
-			continue;
+			let nobj = nobj.unwrap();
+			if nobj == b'[' || nobj == b']' {
+				let moving_boxes = box_tree_is_pushable(&mut grid, &nxy, m);
+				if let Some(mb) = moving_boxes {
+					// find new locations
+					let nmb = mb.iter().map(|v| v.add_dir(&m)).collect_vec();
+					// erase old locations
+					mb.iter().for_each(|&v| {
+						grid.put(&v, b'.');
+						grid.put(&v.add(&Vector::new(1, 0)), b'.');
+					});
+					// put boxes in new locations
+					nmb.iter().for_each(|&v| {
+						grid.put(&v, b'[');
+						grid.put(&v.add(&Vector::new(1, 0)), b']');
+					});
+					// move robot
+					robot_xy = nxy;
+					trace!("{} ok.", m.to_string());
+					continue;
+				}
+				trace!("{} failed.", m.to_string());
+				continue;
+			}
+			// if we reach here, should be a .
+			// move robot
+			robot_xy = robot_xy.add(&m.to_vector());
+			trace!("{} ok.", m.to_string());
 		}
-		// if we reach here, should be a .
-		// move robot
-		robot_xy = robot_xy.add(&m.to_vector());
-		//print!("{} ok. ", m.to_string());
-	}
 
-	//println!("\nfinal position:\n{}", grid.to_string_with_pt(&robot_xy));
-	// calculate GPS score -- sum of each box's (100*by+bx)
-	// NEAREST edge...
-	let score2: i64 = grid.find(b'[').iter().map(|v| (v.0 as i64) + v.1  as i64 * 100_i64 ).sum();
-	println!("part one score: {}", score);
-	println!("part two score: {}", score2);
+		trace!("\nfinal position:\n{}", grid.to_string_with_pt(&robot_xy));
+		if render::enabled() {
+			picture(&grid, robot_xy).draw("final position:");
+		}
+		// calculate GPS score -- sum of each box's (100*by+bx)
+		// NEAREST edge...
+		let score2: i64 = grid.find(b'[').iter().map(|v| (v.0 as i64) + v.1  as i64 * 100_i64 ).sum();
+		info!("part two score: {}", score2);
 
-	(score.to_string(), score2.to_string())
+		Ok(score2.to_string())
+	}
 }
 
 fn push_boxes(grid: &mut Grid, xy: &Vector, m: Move) -> bool {	// returns true if move made, false if impossible
@@ -182,9 +246,9 @@ fn box_tree_is_pushable(grid: &Grid, xyu: &Vector, m: Move) -> Option<Vec<Vector
 	let mut box_list = vec![];
 	let mut xy = xyu.clone();
 	if grid.get_unchecked(&xy) == b']' {
-		xy = Vector(xy.0 - 1, xy.1);
+		xy = Vector::new(xy.0 - 1, xy.1);
 	}
-	//println!("btip called with xyu {:?}, xy {:?}, mv {:?}", *xyu, xy, m);
+	trace!("btip called with xyu {:?}, xy {:?}, mv {:?}", *xyu, xy, m);
 	box_list.push(xy);
 	if m == Move::Left || m == Move::Right {
 		let mut qxy = xy.add_dir(&m);
@@ -229,12 +293,12 @@ fn box_tree_is_pushable(grid: &Grid, xyu: &Vector, m: Move) -> Option<Vec<Vector
 			break;
 		}
 		let qxy = qxy.unwrap();
-		let qobj = [ grid.get(&qxy), grid.get(&qxy.add(&Vector(1,0))) ];
+		let qobj = [ grid.get(&qxy), grid.get(&qxy.add(&Vector::new(1,0))) ];
 		if qobj.iter().any(|o| o.is_none()) {
 			return None;
 		}
 		let qobj = [ qobj[0].unwrap() as char, qobj[1].unwrap() as char ];
-		// println!("testing {:?} qobjs {:?}", qxy, qobj);
+		trace!("testing {:?} qobjs {:?}", qxy, qobj);
 		if qobj.iter().any(|&c| c == '#') {
 			return None;
 		}
@@ -248,12 +312,12 @@ fn box_tree_is_pushable(grid: &Grid, xyu: &Vector, m: Move) -> Option<Vec<Vector
 			continue;
 		}
 		if qobj[0] == ']' {
-			let z = qxy.add(&Vector(-1,0));
+			let z = qxy.add(&Vector::new(-1,0));
 			box_list.push(z);
 			queue.push_back(z.add_dir(&m));
 		}
 		if qobj[1] == '[' {
-			let z = qxy.add(&Vector(1,0));
+			let z = qxy.add(&Vector::new(1,0));
 			box_list.push(z);
 			queue.push_back(z.add_dir(&m));
 		}
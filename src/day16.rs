@@ -1,29 +1,106 @@
 //use itertools::Itertools;
 use crate::level::{*};
 use crate::solve::{*};
+use crate::solver::{Registry, Solver, Param, Params};
+use crate::error::{*};
+use crate::cost::{self, CostModel};
+use crate::search::Budget;
+use crate::render::{self, Animation, Palette, Picture};
+use crossterm::style::Color;
+use crate::image::{self, Image};
 
-pub fn day16(input: &String) -> (String, String) {
-	let level = Level::from_str(input).expect("valid level");
-	let mut best_score: usize = 0;
-	println!("level w: {}, h: {}", level.w, level.h);
-	println!("part 1 & 2 calculating...");
-	//println!("{}", level.to_string());
-	let soln = find_best_path_16(&level, 1_000);
-	let mut best_tiles: usize = 0;
-	if let Some(sol) = soln {
-		//println!("Solution found!");
-		//let ss: String = sol.path.iter().map(|m| m.to_string()).collect();
-		//println!("solution : {}", ss);
-
-		best_score = sol.score as usize;
-		best_tiles = sol.visited.len();
-
-		println!("best score: {}", best_score);
-		println!("best tiles: {}", best_tiles);
-	} else {
-		println!("no solution");
+pub struct Input {
+	level: Level,
+	model: CostModel,
+	soln: Option<Solution>,		// both parts come from the one search, so we keep it here
+}
+
+impl Input {
+	fn solve(&mut self) -> &Solution {
+		if self.soln.is_none() {
+			info!("part 1 & 2 calculating...");
+			// a best path never comes back to a tile, so it can't be deeper than the number of tiles
+			let budget = Budget { max_depth: (self.level.w * self.level.h) as u64, ..Budget::UNLIMITED };
+			let soln = find_best_path(&self.level, &self.model, budget);
+			if render::enabled() {
+				self.draw(&soln);
+			}
+			if image::enabled() {
+				let mut img = Image::from_level(&self.level, &image::Palette::default());
+				img.layer(soln.visited.iter().copied(), image::BLUE, 160);
+				img.layer(soln.pts.iter().copied(), image::GREEN, 200);
+				if let Err(e) = image::save("day16-paths", &img.with_scale(4)) {
+					info!("{}", e);
+				}
+			}
+			self.soln = Some(soln);
+		}
+		self.soln.as_ref().unwrap()
+	}
+	// the tiles on any best path, and the deer walking along one of them
+	fn draw(&self, soln: &Solution) {
+		let mut pic = Picture::from_level(&self.level, &Palette::default());
+		pic.shade(&soln.visited, Color::DarkBlue);
+		pic.shade(&soln.pts, Color::DarkGreen);
+		if let Some(mut anim) = Animation::new() {
+			for (i, &p) in soln.pts.iter().enumerate() {
+				let mut frame = pic.clone();
+				frame.point(p, 'd', Color::Green);
+				anim.frame(&frame, &format!("move {} of {}", i, soln.pts.len() - 1));
+			}
+		}
+		pic.draw(&format!("best paths (score {}):", soln.score));
+	}
+}
+
+pub struct Day16;
+
+pub fn register(r: &mut Registry) {
+	r.add(Day16);
+}
+
+impl Solver for Day16 {
+	type Input = Input;
+
+	fn name(&self) -> &'static str { "day16" }
+	fn day(&self) -> usize { 16 }
+	// the reindeer's rules by default, but e.g. --param turn=0 --param diagonal=2 --param terrain=~5 for a robot
+	fn params(&self) -> &'static [Param] { cost::PARAMS }
+
+	fn parse(&self, input: &str, params: &Params) -> AocResult<Self::Input> {
+		let model = CostModel::from_params(params)?;
+		let level = Level::parse(input, &LevelFormat::default().with_terrain(&model.terrain_chars()))?;
+		debug!("level w: {}, h: {}", level.w, level.h);
+		debug!("cost model: {:?}", model);
+		trace!("{}", level.to_string());
+		Ok(Input { level, model, soln: None })
+	}
+
+	fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+		let mut best_score: usize = 0;
+		let sol = input.solve();
+		if sol.reached_end {
+			trace!("Solution found!");
+			trace!("solution : {}", sol.path.iter().map(|m| m.to_string()).collect::<String>());
+			best_score = sol.score as usize;
+			info!("best score: {}", best_score);
+		} else {
+			info!("no solution: {}", sol.failure());
+		}
+		Ok(best_score.to_string())
+	}
+
+	fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+		let mut best_tiles: usize = 0;
+		let sol = input.solve();
+		if sol.reached_end {
+			best_tiles = sol.visited.len();
+			info!("best tiles: {}", best_tiles);
+		} else {
+			info!("no solution: {}", sol.failure());
+		}
+		Ok(best_tiles.to_string())
 	}
-	(best_score.to_string(), best_tiles.to_string())
 	// ex16b.txt
 	// best score: 11048
 	// best tiles: 64
@@ -31,5 +108,4 @@ pub fn day16(input: &String) -> (String, String) {
 	// ex16.txt
 	// best score: 7036
 	// best tiles: 45
-
-}
\ No newline at end of file
+}
@@ -2,6 +2,8 @@ use itertools::Itertools;
 //use std::collections::{*};
 //use crate::grid::{*};
 //use crate::vector::{*};
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
 #[derive(Debug)]
 struct Computer {
@@ -15,6 +17,9 @@ const REG_A: usize = 0;
 const REG_B: usize = 1;
 const REG_C: usize = 2;
 
+// the instructions that take a combo operand
+const COMBO_INSTRUCTIONS: [u64; 5] = [0, 2, 5, 6, 7];
+
 impl Computer {
 	pub fn new(program: Vec<u8>) -> Computer {
 		Self {
@@ -30,17 +35,18 @@ impl Computer {
 		self.output.clear();
 	}
 	pub fn step(&mut self) -> (bool,Option<u8>) { // return true if running, false if halted. Some if output
-		// are we halted?
-		if self.ip >= self.program.len() {
+		// are we halted? (a jump to the last value also halts, as there is no operand to read)
+		if self.ip + 1 >= self.program.len() {
 			return (false, None);
 		}
 		let instruction = self.program[self.ip];
 		let operand = self.program[self.ip + 1];
 
-		//println!("instruction {}, operand {}, at ip {}", instruction, operand, self.ip);
+		trace!("instruction {}, operand {}, at ip {}", instruction, operand, self.ip);
 		match instruction {
 			0 | 6 | 7 => { // adv: division, operand: combo, 0: output to A, 6: output to B, 7: output to C.
-				let r = self.reg[REG_A] >> self.get_combo_value(operand);
+				// a shift of 64 or more leaves nothing
+				let r = u32::try_from(self.get_combo_value(operand)).ok().and_then(|n| self.reg[REG_A].checked_shr(n)).unwrap_or(0);
 				let reg_num: usize = match instruction {
 					0 => REG_A,
 					6 => REG_B,
@@ -75,9 +81,7 @@ impl Computer {
 				self.ip += 2;
 				return (true, Some(r));
 			},
-			_ => {
-				panic!("Unknown instruction {}", instruction);
-			},
+			_ => unreachable!("the parser only allows instructions 0-7"),
 		};
 
 		(true, None)
@@ -88,103 +92,143 @@ impl Computer {
 			4 => self.reg[REG_A],
 			5 => self.reg[REG_B],
 			6 => self.reg[REG_C],
-			_ => panic!("invalid combo operand"),
+			_ => unreachable!("the parser rejects combo operand 7"),
 		}
 	}
 
 }
 
-pub fn day17(input: &String) -> (String, String) {
-	// read in input
-	let re = regex::Regex::new(r"(-?\d+)").expect("valid regex");
-	let caps: Vec<u64> = re.find_iter(input).map(|m| m.as_str().parse::<u64>().unwrap()).collect_vec();
-
-	println!("part 1 calculating...");
-
-	// initialise computer
-	let program = caps[3..].iter().map(|&u| u as u8).collect_vec();
-	let mut c = Computer::new(program.clone());
-	c.reg[0] = caps[0];
-	c.reg[1] = caps[1];
-	c.reg[2] = caps[2];
-
-	let mut running: bool = true;
-	let mut steps: usize = 0;
-	while running && steps < 1_000_000 {
-		(running, _) = c.step();
-		steps += 1;
-	}
+pub struct Day17;
 
-	let part1_output: String = c.output.iter().map(|u| u.to_string() + ",").collect();
-	let part1_output = part1_output.trim_end_matches(",");
-	println!("output: {}", part1_output);
-
-	println!("part 2 calculating...");
+pub fn register(r: &mut Registry) {
+	r.add(Day17);
+}
 
-	// initialise computer
+impl Solver for Day17 {
+	type Input = Vec<u64>;
 
-	let solution: Option<u64>;
-	let mut c = Computer::new(program.clone());
+	fn name(&self) -> &'static str { "day17" }
+	fn day(&self) -> usize { 17 }
 
-	//println!("desired program: {:?}", c.program);
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		// read in input
+		let re = regex::Regex::new(r"(-?\d+)").expect("valid regex");
+		let caps: Vec<(usize, u64)> = regex_nums(&re, input)?;
+		if caps.len() < 4 {
+			return Err(AocError::new("expected registers A, B, C and a program"));
+		}
+		if let Some(&(offset, n)) = caps[3..].iter().find(|(_, n)| *n > 7) {
+			return Err(AocError::at_offset(input, offset, format!("program values must be 0-7, found {}", n)));
+		}
+		if (caps.len() - 3) % 2 != 0 {
+			return Err(AocError::new("program should be pairs of instruction and operand"));
+		}
+		// combo operand 7 is reserved, and won't appear in valid programs
+		for pair in caps[3..].chunks(2) {
+			let ((_, instruction), (offset, operand)) = (pair[0], pair[1]);
+			if COMBO_INSTRUCTIONS.contains(&instruction) && operand == 7 {
+				return Err(AocError::at_offset(input, offset, format!("combo operand 7 is reserved (instruction {})", instruction)));
+			}
+		}
+		Ok(caps.into_iter().map(|(_, n)| n).collect_vec())
+	}
 
-	// after actually looking at the program
-	// we need to build A 3 bits at a time
-	let plen = c.program.len();
-	let mut a_components = vec![0_u8; plen];
+	fn part1(&self, caps: &mut Self::Input) -> AocResult<String> {
+		info!("part 1 calculating...");
 
-	let build_a = |components: &[u8]| -> u64 {
-		let mut a: u64 = 0;
-		for i in 0..plen {
-			a <<= 3;
-			a |= components[i] as u64;
-		}
-		a
-	};
-	
-	let mut n = 0;
-	loop {
-		let initial_a = build_a(&a_components);
-		c.reset();
-		c.reg[0] = initial_a;
+		// initialise computer
+		let program = caps[3..].iter().map(|&u| u as u8).collect_vec();
+		let mut c = Computer::new(program.clone());
+		c.reg[0] = caps[0];
 		c.reg[1] = caps[1];
 		c.reg[2] = caps[2];
 
+		let mut running: bool = true;
+		let mut steps: usize = 0;
+		while running && steps < 1_000_000 {
+			(running, _) = c.step();
+			steps += 1;
+		}
+
+		let part1_output: String = c.output.iter().map(|u| u.to_string() + ",").collect();
+		let part1_output = part1_output.trim_end_matches(",");
+		info!("output: {}", part1_output);
+		Ok(part1_output.to_string())
+	}
+
+	fn part2(&self, caps: &mut Self::Input) -> AocResult<String> {
+		let program = caps[3..].iter().map(|&u| u as u8).collect_vec();
+
+		info!("part 2 calculating...");
+
+		// initialise computer
+
+		let solution: Option<u64>;
+		let mut c = Computer::new(program.clone());
+
+		trace!("desired program: {:?}", c.program);
+
+		// after actually looking at the program
+		// we need to build A 3 bits at a time
+		let plen = c.program.len();
+		let mut a_components = vec![0_u8; plen];
+
+		let build_a = |components: &[u8]| -> u64 {
+			let mut a: u64 = 0;
+			for i in 0..plen {
+				a <<= 3;
+				a |= components[i] as u64;
+			}
+			a
+		};
+	
+		let mut n = 0;
 		loop {
-			let (running, output) = c.step();
-			if output.is_some() {
-				if c.output.len() > program.len() {
+			let initial_a = build_a(&a_components);
+			c.reset();
+			c.reg[0] = initial_a;
+			c.reg[1] = caps[1];
+			c.reg[2] = caps[2];
+
+			loop {
+				let (running, output) = c.step();
+				if output.is_some() {
+					if c.output.len() > program.len() {
+						break;
+					}
+				}
+				if !running {
 					break;
 				}
 			}
-			if !running {
+			trace!("desired program: {:?}", c.program);
+			trace!("a:               {:?}", a_components);
+			trace!("output:          {:?}", c.output);
+
+			if c.output.len() == plen && c.output[plen-1-n] == program[plen-1-n] {
+				debug!("MATCH at {}", plen-1-n);
+				n += 1;
+			} else {
+				if a_components[n] == u8::MAX {
+					return Err(AocError::new("part 2: no value of A found that outputs the program"));
+				}
+				a_components[n] += 1;
+			}
+		
+			if c.output.len() == program.len() && c.output == program {
+				solution = Some(initial_a);
 				break;
 			}
 		}
-		//println!("desired program: {:?}", c.program);
-		//println!("a:               {:?}", a_components);
-		//println!("output:          {:?}", c.output);
 
-		if c.output.len() == plen && c.output[plen-1-n] == program[plen-1-n] {
-			println!("MATCH at {}", plen-1-n);
-			n += 1;
+		let mut part2_result = "".to_string();
+		if solution.is_some() {
+			info!("part 2 solution: {}", solution.unwrap());
+			part2_result = solution.unwrap().to_string();
 		} else {
-			a_components[n] += 1;
-		}
-		
-		if c.output.len() == program.len() && c.output == program {
-			solution = Some(initial_a);
-			break;
+			info!("no solution");
 		}
-	}
 
-	let mut part2_result = "".to_string();
-	if solution.is_some() {
-		println!("part 2 solution: {}", solution.unwrap());
-		part2_result = solution.unwrap().to_string();
-	} else {
-		println!("no solution");
+		Ok(part2_result)
 	}
-
-	(part1_output.to_string(), part2_result.to_string())
-}
\ No newline at end of file
+}
@@ -4,72 +4,117 @@ use crate::grid::{*};
 use crate::vector::{*};
 use crate::level::{*};
 use crate::solve::{*};
+use crate::search::Budget;
+use crate::solver::{Registry, Solver, Param, Params};
+use crate::error::{*};
 
 use itertools::Itertools;
 
-pub fn day18(input: &String) -> (String, String) {
-	// fetch coords
-	let re = regex::Regex::new(r"(-?\d+)").expect("valid regex");
-	let nums: Vec<i32> = re.find_iter(input).map(|m| m.as_str().parse::<i32>().unwrap()).collect_vec();
-
-	let w = 71;	// ex 7,7
-	let h = 71;
-	let count = 1024.min(nums.len()/2);
-
-	let mut grid = Grid::new(w,h);
-	for i in 0..count {
-		let v = Vector(nums[2*i], nums[2*i+1]);
-		grid.put_unchecked(&v, b'#');
-	}
-	grid.put_unchecked(&Vector(0,0), b'S');
-	grid.put_unchecked(&Vector(w-1,h-1), b'E');
-
-
-	let mut level = Level::from_str(&grid.to_string()).unwrap();
-
-	level.start_pos = Vector(0, 0);
-	level.end_pos = Vector(w-1, h-1);
-	level.deer_pos = Vector(0, 0);
-
-	let soln = find_best_path_18(&level, 1_000);
-
-	let part1_solution = format!("{}", soln.unwrap().score);
-
-	let mut idx_min = count;
-	let mut idx_max = nums.len()/2;
-	let bmp_cache = level.wall_bmp.clone();
-
-	let mut idx;
+pub struct Input {
+	nums: Vec<i32>,
+	count: usize,
+	level: Level,
+	budget: Budget,
+}
+
+// the part 1 search is selectable, so we can compare the different search functions
+pub struct Day18 {
+	name: &'static str,
+	search: fn(&Level, Budget) -> Solution,
+}
+
+const PARAMS: &[Param] = &[
+	Param { name: "w", default: "71", help: "grid width (the example is 7)" },
+	Param { name: "h", default: "71", help: "grid height (the example is 7)" },
+	Param { name: "count", default: "1024", help: "bytes fallen for part 1 (the example is 12)" },
+	Param { name: "max_depth", default: "0", help: "give up on paths of more moves than this in part 1 (0 for no limit)" },
+	Param { name: "max_nodes", default: "0", help: "give up after expanding this many states in part 1 (0 for no limit)" },
+];
+
+pub fn register(r: &mut Registry) {
+	r.add(Day18 { name: "day18", search: find_best_path_18 });
+	r.add(Day18 { name: "day18-any", search: find_any_path_18 });
+}
+
+impl Solver for Day18 {
+	type Input = Input;
+
+	fn name(&self) -> &'static str { self.name }
+	fn day(&self) -> usize { 18 }
+	fn params(&self) -> &'static [Param] { PARAMS }
+
+	fn parse(&self, input: &str, params: &Params) -> AocResult<Self::Input> {
+		// fetch coords
+		let re = regex::Regex::new(r"(-?\d+)").expect("valid regex");
+		let caps: Vec<(usize, i32)> = regex_nums(&re, input)?;
+		if caps.len() % 2 != 0 {
+			return Err(AocError::new("expected pairs of coordinates (x,y)"));
+		}
 
-	// bisect
-	loop {
-		idx = (idx_min + idx_max)/2;
-		if idx == idx_min {
-			//status2 = format!("Found sweet spot min {} max {}", idx_min, idx_max);
-			idx = idx_max;
-			break;
+		let w: i32 = params.get("w")?;
+		let h: i32 = params.get("h")?;
+		if w < 3 || h < 3 {
+			return Err(AocError::new("w and h must be at least 3"));
 		}
-		level.wall_bmp = bmp_cache.clone();
-		for fill_idx in count..=idx {
-			let v = Vector(nums[2*fill_idx], nums[2*fill_idx+1]);
-			level.wall_bmp.set_v(v);
+		for c in caps.chunks(2) {
+			if c[0].1 < 0 || c[0].1 >= w || c[1].1 < 0 || c[1].1 >= h {
+				return Err(AocError::at_offset(input, c[0].0, format!("coordinate {},{} is outside the {}x{} grid", c[0].1, c[1].1, w, h)));
+			}
 		}
-		let ok = find_any_path_18(&level, 10_000).is_some();
+		let nums: Vec<i32> = caps.into_iter().map(|(_, n)| n).collect_vec();
+		let count = params.get::<usize>("count")?.min(nums.len()/2);
 
-		if ok { idx_min = idx; }
-		else { idx_max = idx; }
-	}
+		let mut grid = Grid::new(w,h);
+		for i in 0..count {
+			let v = Vector::new(nums[2*i], nums[2*i+1]);
+			grid.put_unchecked(&v, b'#');
+		}
+		grid.put_unchecked(&Vector::new(0,0), b'S');
+		grid.put_unchecked(&Vector::new(w-1,h-1), b'E');
 
+		let level = Level::from_str(&grid.to_string())?;
 
-	let part2_solution = if 2*idx+1 < nums.len() {
-		format!("{},{}", nums[2*idx], nums[2*idx+1])
-	} else {
-		"no solution found".to_string()
-	};
+		// 0 is no limit
+		let budget = Budget {
+			max_depth: Some(params.get::<u64>("max_depth")?).filter(|&d| d > 0).unwrap_or(u64::MAX),
+			max_nodes: Some(params.get::<usize>("max_nodes")?).filter(|&n| n > 0).unwrap_or(usize::MAX),
+		};
 
-	println!("part 1 solution: {}", part1_solution);
-	println!("part 2 solution: {}", part2_solution);
+		Ok(Input { nums, count, level, budget })
+	}
 
-	(part1_solution, part2_solution)
+	fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+		let soln = (self.search)(&input.level, input.budget);
+		// a part 1 with no path (or one we gave up on) is still reported, and part 2 can still run
+		let part1_solution = if soln.reached_end {
+			format!("{}", soln.score)
+		} else {
+			info!("part 1: no path found to the exit: {}", soln.failure());
+			"no solution found".to_string()
+		};
+		info!("part 1 solution: {}", part1_solution);
+		Ok(part1_solution)
+	}
 
-}
\ No newline at end of file
+	fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+		let nums = &input.nums;
+		let count = input.count;
+
+		// the bytes still to fall, after those for part 1
+		let bytes: Vec<Vector> = nums.chunks(2).skip(count).map(|c| Vector::new(c[0], c[1])).collect();
+		let blocking = input.level.first_blocking_wall(&bytes).map_err(|e| AocError::new(format!("part 2: {}", e)))?;
+		let idx = match blocking {
+			Some(i) => count + i,
+			None => nums.len() / 2,
+		};
+
+		let part2_solution = if 2*idx+1 < nums.len() {
+			format!("{},{}", nums[2*idx], nums[2*idx+1])
+		} else {
+			"no solution found".to_string()
+		};
+
+		info!("part 2 solution: {}", part2_solution);
+		Ok(part2_solution)
+	}
+}
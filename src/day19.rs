@@ -5,6 +5,8 @@ use std::collections::{*};
 use std::io::stdout;
 #[allow(unused_imports)]
 use crossterm::{execute,style,style::Stylize};
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
 #[derive(Copy,Clone,Eq,PartialEq,Hash,Ord,PartialOrd)]
 struct Array8 {
@@ -48,13 +50,13 @@ impl Array8 {
 	}
 }
 
-struct Solver {
+struct TowelSolver {
 	tps: Vec<Array8>,
 	length_map: BTreeMap<Array8, u8>,
 	pub combo_map: BTreeMap<usize, u64>,
 }
 
-impl Solver {
+impl TowelSolver {
 	pub fn new(tps: &Vec<&[u8]>) -> Self {
 		let mut nps = tps.iter().map(|&tp| Array8::from_slice(tp)).collect_vec();
 		let mut tps_set: BTreeSet<Array8> = BTreeSet::new();
@@ -146,52 +148,85 @@ fn find_any_soln(p: &[u8], tps: &Vec<&[u8]>) -> bool {
 	false
 }
 
-pub fn day19(input: &String) -> (String, String) {
-	// towel pattern puzzle
-	let lines = input.lines().collect_vec();
-	let re = regex::Regex::new(r"([rbgwu]+)").expect("valid regex");
-	let mut tps = re.find_iter(lines[0]).map(|m| m.as_str().as_bytes()).collect_vec();
-	let max_len = tps.iter().max_by(|&&a, &&b| a.len().cmp(&b.len())).unwrap().len();
-	let min_len = tps.iter().min_by(|&&a, &&b| a.len().cmp(&b.len())).unwrap().len();
-	let patterns = lines[2..].iter().map(|&s| s.as_bytes()).collect_vec();
-	tps.sort();
-
-	// print basic stats
-
-	println!("tps: {}", tps.len());
-	println!("max tp len: {}", max_len);
-	println!("min tp len: {}", min_len);
-	println!("patterns: {}", patterns.len());
-
-	println!("part 1 calculating...");
-
-	let mut p1score = 0;
-	for &p in patterns.iter() {
-		if find_any_soln(p, &tps) {
-			p1score += 1;
+pub struct Input {
+	tps: Vec<Vec<u8>>,
+	patterns: Vec<Vec<u8>>,
+}
+
+pub struct Day19;
+
+pub fn register(r: &mut Registry) {
+	r.add(Day19);
+}
+
+impl Solver for Day19 {
+	type Input = Input;
+
+	fn name(&self) -> &'static str { "day19" }
+	fn day(&self) -> usize { 19 }
+
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		// towel pattern puzzle
+		let lines = input.lines().collect_vec();
+		let re = regex::Regex::new(r"([rbgwu]+)").expect("valid regex");
+		let Some(first) = lines.first() else {
+			return Err(AocError::new("empty input"));
+		};
+		let mut tps = re.find_iter(first).map(|m| m.as_str().as_bytes().to_vec()).collect_vec();
+		if tps.is_empty() {
+			return Err(AocError::in_line(0, "expected a list of towel patterns"));
 		}
-	}
+		if lines.len() < 2 || !lines[1].trim().is_empty() {
+			return Err(AocError::in_line(1, "expected a blank line after the towel patterns"));
+		}
+		let max_len = tps.iter().max_by(|&a, &b| a.len().cmp(&b.len())).unwrap().len();
+		let min_len = tps.iter().min_by(|&a, &b| a.len().cmp(&b.len())).unwrap().len();
+		let patterns = lines[2..].iter().map(|&s| s.as_bytes().to_vec()).collect_vec();
+		tps.sort();
 
-	println!("part 1 score: {}", p1score);
+		// print basic stats
 
-	// part 2
+		debug!("tps: {}", tps.len());
+		debug!("max tp len: {}", max_len);
+		debug!("min tp len: {}", min_len);
+		debug!("patterns: {}", patterns.len());
 
-	println!("part 2 calculating...");
+		Ok(Input { tps, patterns })
+	}
 
-	let mut p2score = 0;
-	let mut solver = Solver::new(&tps);
-		
-	for (_i,&p) in patterns.iter().enumerate() {
-		//execute!(stdout(),	style::PrintStyledContent(format!("pattern {}:",i).cyan()),	).unwrap();
+	fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+		info!("part 1 calculating...");
 
-		let count: u64 = solver.get_num_combos(p,0);
-		solver.combo_map.clear();	// must reset this after every pattern we check
+		let tps = input.tps.iter().map(|t| t.as_slice()).collect_vec();
+		let mut p1score = 0;
+		for p in input.patterns.iter() {
+			if find_any_soln(p, &tps) {
+				p1score += 1;
+			}
+		}
 
-		//execute!(stdout(), style::PrintStyledContent(format!(" {}\n", count).green())).unwrap();
-		p2score += count;
+		info!("part 1 score: {}", p1score);
+		Ok(p1score.to_string())
 	}
 
-	println!("part 2 score: {}", p2score);
+	fn part2(&self, input: &mut Self::Input) -> AocResult<String> {
+		info!("part 2 calculating...");
+
+		let tps = input.tps.iter().map(|t| t.as_slice()).collect_vec();
+		let mut p2score = 0;
+		let mut solver = TowelSolver::new(&tps);
+
+		for (_i,p) in input.patterns.iter().enumerate() {
+			//execute!(stdout(),	style::PrintStyledContent(format!("pattern {}:",i).cyan()),	).unwrap();
+
+			let count: u64 = solver.get_num_combos(p,0);
+			solver.combo_map.clear();	// must reset this after every pattern we check
 
-	(p1score.to_string(), p2score.to_string())
-}
\ No newline at end of file
+			//execute!(stdout(), style::PrintStyledContent(format!(" {}\n", count).green())).unwrap();
+			p2score += count;
+		}
+
+		info!("part 2 score: {}", p2score);
+		Ok(p2score.to_string())
+	}
+}
@@ -1,6 +1,7 @@
-use itertools::Itertools;
 use crate::vector::{*};
 use std::collections::BTreeMap;
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
 // +---+---+---+
 // | 7 | 8 | 9 |
@@ -133,34 +134,34 @@ enum ControlType {
 impl Robot {
 	pub fn no_go(&self) -> Vector {
 		if self.control_type == ControlType::Numpad {
-			Vector(0,3)
+			Vector::new(0,3)
 		} else {
-			Vector(0,0)
+			Vector::new(0,0)
 		}
 	}
 	pub fn c_to_v_numpad(c: char) -> Vector {
 		match c {
-			'7' => Vector(0,0),
-			'8' => Vector(1,0),
-			'9' => Vector(2,0),
-			'4' => Vector(0,1),
-			'5' => Vector(1,1),
-			'6' => Vector(2,1),
-			'1' => Vector(0,2),
-			'2' => Vector(1,2),
-			'3' => Vector(2,2),
-			'0' => Vector(1,3),
-			'A' => Vector(2,3),
+			'7' => Vector::new(0,0),
+			'8' => Vector::new(1,0),
+			'9' => Vector::new(2,0),
+			'4' => Vector::new(0,1),
+			'5' => Vector::new(1,1),
+			'6' => Vector::new(2,1),
+			'1' => Vector::new(0,2),
+			'2' => Vector::new(1,2),
+			'3' => Vector::new(2,2),
+			'0' => Vector::new(1,3),
+			'A' => Vector::new(2,3),
 			_ => panic!(),
 		}
 	}
 	pub fn c_to_v_directional(c: char) -> Vector {
 		match c {
-			'^' => Vector(1,0),
-			'A' => Vector(2,0),
-			'<' => Vector(0,1),
-			'v' => Vector(1,1),
-			'>' => Vector(2,1),
+			'^' => Vector::new(1,0),
+			'A' => Vector::new(2,0),
+			'<' => Vector::new(0,1),
+			'v' => Vector::new(1,1),
+			'>' => Vector::new(2,1),
 			_ => panic!(),
 		}
 	}
@@ -265,69 +266,81 @@ impl Robot {
 	}
 	pub fn new(control_type: ControlType) -> Self {
 		let v = if control_type == ControlType::Numpad {
-			Vector(2,3)
+			Vector::new(2,3)
 		} else {
-			Vector(2,0)
+			Vector::new(2,0)
 		};
 		Self { posn: v, control_type }
 	}
 }
 
-pub fn day21(input: &String) -> (String, String) {
-	let codes_s: Vec<&str> = input.lines().collect_vec();
-	let codes: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
-	println!("part 1 calculating...");
-	let robot1 = Robot::new(ControlType::Numpad);
-	let robot2 = Robot::new(ControlType::Directional);
-	let robot3 = Robot::new(ControlType::Directional);
+// total complexity of the codes, for a chain of robots (numpad robot first)
+fn chain_complexity(codes: &Vec<String>, robots: Vec<Robot>) -> usize {
 	let mut robot_chain = RobotChain {
-		robots: vec![robot1, robot2, robot3],
+		robots,
 		pcache: BTreeMap::new(),
 	};
-	let mut p1soln: usize = 0;
-	for (i,&ref code) in codes.iter().enumerate() {
-		println!("'{}'...",  codes_s[i]);
+	let mut soln: usize = 0;
+	for code in codes.iter() {
+		debug!("'{}'...",  code);
 		let mut count = 0;
-		for c in code.iter() {
-			count += robot_chain.do_path(*c, 0);
+		for c in code.chars() {
+			count += robot_chain.do_path(c, 0);
 		}
-		let n1 = codes_s[i][0..3].parse::<usize>().unwrap();
-		let complexity: usize = count * (codes_s[i][0..3]).parse::<usize>().unwrap();
-		println!("complexity = {} * length {} = {}", n1, count, complexity);
-		p1soln += complexity;
+		let n1 = code[0..3].parse::<usize>().unwrap();
+		let complexity: usize = count * n1;
+		debug!("complexity = {} * length {} = {}", n1, count, complexity);
+		soln += complexity;
 	}
+	soln
+}
 
-	println!("part 1 solution: {}", p1soln);
+pub struct Day21;
 
-	let t0 = crate::time::get_time_ms();
+pub fn register(r: &mut Registry) {
+	r.add(Day21);
+}
 
-	println!("part 2 calculating...");
+impl Solver for Day21 {
+	type Input = Vec<String>;
 
-	// build a robot chain
+	fn name(&self) -> &'static str { "day21" }
+	fn day(&self) -> usize { 21 }
 
-	let mut robot_vec = vec![ Robot::new(ControlType::Numpad) ];
-	robot_vec.extend(vec![ Robot::new(ControlType::Directional); 25]);
-	let mut robot_chain = RobotChain {
-		robots: robot_vec,
-		pcache: BTreeMap::new(),
-	};
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		input.lines().enumerate().filter(|(_, l)| !l.is_empty()).map(|(y, l)| {
+			if let Some((x, c)) = l.char_indices().find(|(_, c)| !"0123456789A".contains(*c)) {
+				return Err(AocError::at(y, x, format!("unexpected character '{}' in code", c)));
+			}
+			if l.len() < 3 || !l[0..3].bytes().all(|b| b.is_ascii_digit()) {
+				return Err(AocError::in_line(y, "code should start with three digits"));
+			}
+			Ok(l.to_string())
+		}).collect()
+	}
 
-	let mut p2soln: usize = 0;
-	for (i,&ref code) in codes.iter().enumerate() {
-		let mut count = 0;
-		println!("'{}'...", codes_s[i]);
-		for c in code.iter() {
-			count += robot_chain.do_path(*c, 0);
-		}
-		let n1 = codes_s[i][0..3].parse::<usize>().unwrap();
-		let complexity: usize = count * (codes_s[i][0..3]).parse::<usize>().unwrap();
-		println!("complexity = {} * length {} = {}", n1, count, complexity);
-		p2soln += complexity;
+	fn part1(&self, codes: &mut Self::Input) -> AocResult<String> {
+		info!("part 1 calculating...");
+		let robot1 = Robot::new(ControlType::Numpad);
+		let robot2 = Robot::new(ControlType::Directional);
+		let robot3 = Robot::new(ControlType::Directional);
+		let p1soln = chain_complexity(codes, vec![robot1, robot2, robot3]);
+
+		info!("part 1 solution: {}", p1soln);
+		Ok(p1soln.to_string())
 	}
 
-	let t1 = crate::time::get_time_ms();
-	println!("part 2 solution: {}", p2soln);
-	println!("time for part 2 : {:.0} ms", t1 - t0);
+	fn part2(&self, codes: &mut Self::Input) -> AocResult<String> {
+		info!("part 2 calculating...");
+
+		// build a robot chain
 
-	(p1soln.to_string(), p2soln.to_string())
+		let mut robot_vec = vec![ Robot::new(ControlType::Numpad) ];
+		robot_vec.extend(vec![ Robot::new(ControlType::Directional); 25]);
+		let p2soln = chain_complexity(codes, robot_vec);
+
+		info!("part 2 solution: {}", p2soln);
+
+		Ok(p2soln.to_string())
+	}
 }
@@ -1,139 +1,163 @@
 use itertools::Itertools;
 use std::collections::{*};
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
 #[derive(Debug,Clone)]
-struct Node {
+pub struct Node {
 	conns: BTreeSet<[u8;2]>,
 }
 
-pub fn day23(input: &String) -> (String, String) {
-	let pairs: Vec<([u8;2],[u8;2])> = input.lines().filter(|s| s.len() > 0).map(|s| s.as_bytes()).map(|s| ([s[0], s[1]], [s[3], s[4]])).collect_vec();
-	let mut nodes: BTreeMap<[u8;2],Node> = BTreeMap::new();
+fn nid2s(nid: &[u8;2]) -> String {
+	let mut s = String::new();
+	s.push(nid[0] as char);
+	s.push(nid[1] as char);
+	s
+}
 
-	// create node graph
-	for (a,b) in pairs.iter() {
-		if let Some(&mut ref mut x) = nodes.get_mut(a) {
-			x.conns.insert([b[0], b[1]]);
-		} else {
-			nodes.insert(*a, Node { conns: BTreeSet::from( [[b[0], b[1]]] ) } );
-		}
-		if let Some(&mut ref mut x) = nodes.get_mut(b) {
-			x.conns.insert([a[0], a[1]]);
-		} else {
-			nodes.insert(*b, Node { conns: BTreeSet::from( [[a[0], a[1]]] ) } );
+pub struct Day23;
+
+pub fn register(r: &mut Registry) {
+	r.add(Day23);
+}
+
+impl Solver for Day23 {
+	type Input = BTreeMap<[u8;2],Node>;
+
+	fn name(&self) -> &'static str { "day23" }
+	fn day(&self) -> usize { 23 }
+
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		let pairs: Vec<([u8;2],[u8;2])> = input.lines().enumerate().filter(|(_, s)| s.len() > 0).map(|(y, s)| {
+			let s = s.as_bytes();
+			if let Some(x) = (0..5).find(|&x| x >= s.len() || (x == 2) != (s[x] == b'-') || (x != 2 && !s[x].is_ascii_lowercase())) {
+				return Err(AocError::at(y, x, "expected a connection 'ab-cd'"));
+			}
+			if s.len() > 5 {
+				return Err(AocError::at(y, 5, "unexpected characters after connection"));
+			}
+			Ok(([s[0], s[1]], [s[3], s[4]]))
+		}).collect::<AocResult<Vec<_>>>()?;
+		let mut nodes: BTreeMap<[u8;2],Node> = BTreeMap::new();
+
+		// create node graph
+		for (a,b) in pairs.iter() {
+			if let Some(&mut ref mut x) = nodes.get_mut(a) {
+				x.conns.insert([b[0], b[1]]);
+			} else {
+				nodes.insert(*a, Node { conns: BTreeSet::from( [[b[0], b[1]]] ) } );
+			}
+			if let Some(&mut ref mut x) = nodes.get_mut(b) {
+				x.conns.insert([a[0], a[1]]);
+			} else {
+				nodes.insert(*b, Node { conns: BTreeSet::from( [[a[0], a[1]]] ) } );
+			}
 		}
+		Ok(nodes)
 	}
 
-	println!("part 1 calculating...");
-	// println!("nodes len: {}", nodes.len());
-	// for (k,v) in &nodes {
-	// 	println!("{}: {}", nid2s(k), v.conns.len())
-	// }
-
-	fn nid2s(nid: &[u8;2]) -> String {
-		let mut s = String::new();
-		s.push(nid[0] as char);
-		s.push(nid[1] as char);
-		s
-	}
+	fn part1(&self, nodes: &mut Self::Input) -> AocResult<String> {
+		info!("part 1 calculating...");
+		trace!("nodes len: {}", nodes.len());
+		if crate::log::enabled(crate::log::Level::Trace) {
+			for (k,v) in nodes.iter() {
+				trace!("{}: {}", nid2s(k), v.conns.len());
+			}
+		}
+
+		let mut trios: Vec<Vec<[u8;2]>> = vec![];
+		let mut t_trios: Vec<Vec<[u8;2]>> = vec![];
+		// find a trio of nodes
+		for (id, node) in nodes.iter() {
+			// see if it's a trio
+			// if it is, then two of our conns will connect to each other!
+			if node.conns.len() < 2 {
+				continue; // needs to have at least two connections!
+			}
 
-	let mut trios: Vec<Vec<[u8;2]>> = vec![];
-	let mut t_trios: Vec<Vec<[u8;2]>> = vec![];
-	// find a trio of nodes
-	for (id, node) in nodes.iter() {
-		// see if it's a trio
-		// if it is, then two of our conns will connect to each other!
-		if node.conns.len() < 2 {
-			continue; // needs to have at least two connections!
+			// get all pair combinations of our conns
+			let pairs = node.conns.iter().combinations(2)	// we want each pair to have reference to each other
+				.filter(|v| nodes.get(v[0]).unwrap().conns.contains(v[1]))
+				.map(|v| [v[0], v[1]])
+				.collect_vec();
+
+			for [p1,p2] in pairs {
+				// we should check that trios doesn't contain a variation of this trio!
+				let combos = vec![*id,*p1,*p2].into_iter().permutations(3).collect_vec();
+				let ok = combos.iter().all(|c| !trios.contains(c));
+				if ok {
+					trace!("trio found: {}-{}-{}", nid2s(&id), nid2s(&p1), nid2s(&p2));
+					trios.push(vec![*id, *p1, *p2]);
+				}
+			}
 		}
 
-		// get all pair combinations of our conns
-		let pairs = node.conns.iter().combinations(2)	// we want each pair to have reference to each other
-			.filter(|v| nodes.get(v[0]).unwrap().conns.contains(v[1]))
-			.map(|v| [v[0], v[1]])
-			.collect_vec();
-
-		for [p1,p2] in pairs {
-			// we should check that trios doesn't contain a variation of this trio!
-			let combos = vec![*id,*p1,*p2].into_iter().permutations(3).collect_vec();
-			let ok = combos.iter().all(|c| !trios.contains(c));
-			if ok {
-				//println!("trio found: {}-{}-{}", nid2s(&id), nid2s(&p1), nid2s(&p2));
-				trios.push(vec![*id, *p1, *p2]);
+		let nt_trios = trios.iter().filter(|&t| t.iter().any(|n| n[0] == b't')).map(|t| t.clone()).collect_vec();
+		t_trios.extend(nt_trios);
+
+		if crate::log::enabled(crate::log::Level::Trace) {
+			for t in &t_trios {
+				trace!("t-trio: {}-{}-{}", nid2s(&t[0]), nid2s(&t[1]), nid2s(&t[2]));
 			}
 		}
+
+		let p1result = t_trios.len();
+		info!("part 1 result: {}", p1result);
+		Ok(p1result.to_string())
 	}
 
-	let nt_trios = trios.iter().filter(|&t| t.iter().any(|n| n[0] == b't')).map(|t| t.clone()).collect_vec();
-	t_trios.extend(nt_trios);
-
-	// for t in &t_trios {
-	// 	println!("t-trio: {}-{}-{}", nid2s(&t[0]), nid2s(&t[1]), nid2s(&t[2]));
-	// }
-
-	let p1result = t_trios.len();
-	println!("part 1 result: {}", p1result);
-
-	// part 2
-
-	println!("part 2 calculating...");
-
-	// find the largest set
-	// every computer in the set is joined to every other computer in the set...
-	// conns are bi-directional...
-	// there are 520 nodes in total, and each node has connection to 13 other computers
-	// so the largest possible set is 14
-	// each member of the set will have a large number of their conns
-	// the same as each other member of the set
-
-	let mut best_set: BTreeSet<[u8;2]> = BTreeSet::new();
-
-	for (id, node) in &nodes {
-		// count how many of our conns, are in our conns's conns
-		let mut test_set: BTreeSet<[u8;2]> = BTreeSet::from(node.conns.clone());
-		test_set.insert(*id);		// test set of size 14
-		let mut reducing_set;
-
-		// how do we determine which subnodes are strong and which are weak ?
-		// we need a threshold!
-
-		// print test set
-		// print!("test_set: ");
-		// for n in &test_set {
-		// 	print!("{} ", nid2s(n));
-		// }
-		// println!("");
-
-		for threshold in (3..=13).rev() {
-			reducing_set = test_set.clone();	// test set that will be reduced to what is common between most
-			let mut ok_count = 0;
-			for subnodeid in test_set.iter() {
-				let sub_set: BTreeSet<[u8; 2]> = nodes.get(subnodeid).unwrap().conns.intersection(&reducing_set).map(|&n| n).collect();
-				if sub_set.len() >= threshold {
-					reducing_set = sub_set;
-					reducing_set.insert(*subnodeid);
-					ok_count += 1;
-					// print matches
-					// print!("  subnode {} matches ", nid2s(subnodeid));
-					// for n in &reducing_set {
-					// 	print!("{} ", nid2s(n));
-					// }
-					// println!("");
+	fn part2(&self, nodes: &mut Self::Input) -> AocResult<String> {
+		info!("part 2 calculating...");
+
+		// find the largest set
+		// every computer in the set is joined to every other computer in the set...
+		// conns are bi-directional...
+		// there are 520 nodes in total, and each node has connection to 13 other computers
+		// so the largest possible set is 14
+		// each member of the set will have a large number of their conns
+		// the same as each other member of the set
+
+		let mut best_set: BTreeSet<[u8;2]> = BTreeSet::new();
+
+		for (id, node) in nodes.iter() {
+			// count how many of our conns, are in our conns's conns
+			let mut test_set: BTreeSet<[u8;2]> = BTreeSet::from(node.conns.clone());
+			test_set.insert(*id);		// test set of size 14
+			let mut reducing_set;
+
+			// how do we determine which subnodes are strong and which are weak ?
+			// we need a threshold!
+
+			// print test set
+			trace!("test_set: {}", test_set.iter().map(nid2s).collect::<Vec<String>>().join(" "));
+
+			for threshold in (3..=13).rev() {
+				reducing_set = test_set.clone();	// test set that will be reduced to what is common between most
+				let mut ok_count = 0;
+				for subnodeid in test_set.iter() {
+					let sub_set: BTreeSet<[u8; 2]> = nodes.get(subnodeid).unwrap().conns.intersection(&reducing_set).map(|&n| n).collect();
+					if sub_set.len() >= threshold {
+						reducing_set = sub_set;
+						reducing_set.insert(*subnodeid);
+						ok_count += 1;
+						// print matches
+						trace!("  subnode {} matches {}", nid2s(subnodeid), reducing_set.iter().map(nid2s).collect::<Vec<String>>().join(" "));
+					}
 				}
-			}
-			if ok_count >= threshold {
-				if reducing_set.len() > best_set.len() {
-					// found best set so far
-					// println!("found best set so far, size {}", reducing_set.len());
-					best_set = reducing_set.clone();
+				if ok_count >= threshold {
+					if reducing_set.len() > best_set.len() {
+						// found best set so far
+						trace!("found best set so far, size {}", reducing_set.len());
+						best_set = reducing_set.clone();
+					}
 				}
 			}
 		}
-	}
 
-	println!("best set size {}", best_set.len());
-	let s: String = itertools::intersperse(best_set.iter().map(|id| nid2s(id)), ",".to_string()).collect();
-	println!("part 2 result: {}", s);
+		info!("best set size {}", best_set.len());
+		let s: String = itertools::intersperse(best_set.iter().map(|id| nid2s(id)), ",".to_string()).collect();
+		info!("part 2 result: {}", s);
 
-	(p1result.to_string(), s)
-}
\ No newline at end of file
+		Ok(s)
+	}
+}
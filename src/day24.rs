@@ -1,6 +1,8 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use itertools::Itertools;
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum Operation {
@@ -42,7 +44,7 @@ impl Operation {
 // U (unknown)
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Wire {
+pub struct Wire {
 	pub id: String,
 	pub value: Option<bool>,
 	pub output_gates: Vec<Rc<RefCell<Gate>>>,
@@ -79,7 +81,7 @@ impl GateRole {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Gate {
+pub struct Gate {
 	idx: usize,
 	output_value: Option<bool>,
 	//input_ids: [ String; 2 ],
@@ -102,7 +104,7 @@ impl Gate {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Circuit {
+pub struct Circuit {
 	pub wires: Vec<Rc<RefCell<Wire>>>,
 	pub gates: Vec<Rc<RefCell<Gate>>>,
 }
@@ -146,7 +148,7 @@ impl Circuit {
 					}
 				}
 			}
-			// println!("gates processed: {}", gates_processed);
+			trace!("gates processed: {}", gates_processed);
 		}
 	}
 	fn _set_input(&mut self, input_x: u64, input_y: u64) {
@@ -167,13 +169,13 @@ impl Circuit {
 			self.wires[*idx].borrow_mut().value = Some(((input_y >> i) & 0x01) != 0);
 		}		
 	}
-	fn get_output(&self) -> u64 {
+	fn get_output(&self) -> Option<u64> {
 		// get all the zed wires
 		let mut outputs = self.wires.iter().filter(|&ref k| k.borrow().id.chars().nth(0)==Some('z')).map(|&ref k| (k.borrow().id.clone(), k.borrow().value)).collect_vec();
 		// sort them
 		outputs.sort_by(|a,b| a.0.cmp(&b.0));
-		// find solution
-		outputs.iter().enumerate().map(|(i, k)| ((k.1.unwrap() as u64) << i)).sum()
+		// find solution, if all the outputs have a value
+		outputs.iter().enumerate().map(|(i, k)| k.1.map(|v| (v as u64) << i)).sum()
 	}
 	fn _get_input_x(&self) -> u64 {	// for testing
 		// get all the x wires
@@ -195,7 +197,7 @@ impl Circuit {
 		// self.set_input(a,b);
 		//self.reset();
 		self.run_calculation();
-		self.get_output() == expected
+		self.get_output() == Some(expected)
 	}
 	fn _swap_gate_outputs(&self, a: Rc<RefCell<Gate>>, b: Rc<RefCell<Gate>>) {
 		let av = a.borrow().output_id.clone();
@@ -205,277 +207,308 @@ impl Circuit {
 	}
 }
 
-pub fn day24(input: &String) -> (String, String) {
-	// wires and gates
-	let lines = input.lines().collect_vec();
-
-	//let mut wires: BTreeMap<String, Wire> = BTreeMap::new();
-	//let mut gates: BTreeMap<u16, Gate> = BTreeMap::new();
-	let mut circuit = Circuit::new();
-
-	// load in initial wires
-	let mut row = 0;
-	while !lines[row].is_empty() {
-		let wire_id = lines[row][0..=2].to_string();
-		let wire_value = lines[row][5..=5].to_string().parse::<u8>().unwrap() != 0;
-		circuit.wires.push( Rc::from(RefCell::from( Wire {
-			id: wire_id,
-			value: Some(wire_value),
-			output_gates: vec![],
-			role: 'U',
-			n: None,
-		})));
-		row += 1;
-	}
+pub struct Day24;
 
-	// load in gates
-	row += 1;
-	let mut gate_idx: usize = 0;
-	let re = regex::Regex::new(r"([a-z0-9]+) (XOR|AND|OR) ([a-z0-9]+) -> ([a-z0-9]+)").expect("valid regex");
-	while row < lines.len() && !lines[row].is_empty() {
-		let row_data = re.captures(lines[row]).unwrap().iter().map(|m| m.unwrap().as_str()).collect_vec();
-		let input_a_id = row_data[1];
-		let op = row_data[2];
-		let input_b_id = row_data[3];
-		let output_id = row_data[4];
-
-		// create the gate
-		let gate = Rc::from(RefCell::from( Gate {
-			idx: gate_idx,
-			output_value: None,
-			//input_ids: [ input_a_id.to_string(), input_b_id.to_string() ],
-			output_id: output_id.to_string(),
-			op: Operation::from_str(&op),
-			role: GateRole::UNK,
-			n: None,
-		}));
-
-		// initialise associated wires
-		for input_id in [ input_a_id, input_b_id ].into_iter() {
-			if let Some(w) = circuit.get_wire_by_id(input_id) {
-				w.borrow_mut().output_gates.push(gate.clone());
-			} else {
-				circuit.wires.push(Rc::from(RefCell::from( Wire {
-					id: input_id.to_string(),
-					value: None,
-					output_gates: vec![gate.clone()],
-					role: 'U',
-					n: None,
-				})));
-			}
-		}
+pub fn register(r: &mut Registry) {
+	r.add(Day24);
+}
+
+impl Solver for Day24 {
+	type Input = Circuit;
 
-		if let None = circuit.get_wire_by_id(output_id) {
+	fn name(&self) -> &'static str { "day24" }
+	fn day(&self) -> usize { 24 }
+
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		// wires and gates
+		let lines = input.lines().collect_vec();
+
+		//let mut wires: BTreeMap<String, Wire> = BTreeMap::new();
+		//let mut gates: BTreeMap<u16, Gate> = BTreeMap::new();
+		let mut circuit = Circuit::new();
+
+		// load in initial wires
+		let mut row = 0;
+		let re_wire = regex::Regex::new(r"^([a-z0-9]+): ([01])$").expect("valid regex");
+		while row < lines.len() && !lines[row].is_empty() {
+			let Some(caps) = re_wire.captures(lines[row]) else {
+				return Err(AocError::in_line(row, "expected a wire 'id: 0|1'"));
+			};
+			let wire_id = caps[1].to_string();
+			let wire_value = &caps[2] == "1";
+			if (wire_id.starts_with('x') || wire_id.starts_with('y')) && (wire_id.len() != 3 || wire_id[1..].parse::<usize>().is_err()) {
+				return Err(AocError::in_line(row, format!("input wire '{}' should be x or y followed by two digits", wire_id)));
+			}
 			circuit.wires.push( Rc::from(RefCell::from( Wire {
-				id: output_id.to_string(),
-				value: None,
+				id: wire_id,
+				value: Some(wire_value),
 				output_gates: vec![],
 				role: 'U',
 				n: None,
-			} )));
+			})));
+			row += 1;
 		}
 
-		circuit.gates.push(gate);
+		// load in gates
 		row += 1;
-		gate_idx += 1;
-	}
+		let mut gate_idx: usize = 0;
+		let re = regex::Regex::new(r"([a-z0-9]+) (XOR|AND|OR) ([a-z0-9]+) -> ([a-z0-9]+)").expect("valid regex");
+		while row < lines.len() && !lines[row].is_empty() {
+			let Some(caps) = re.captures(lines[row]) else {
+				return Err(AocError::in_line(row, "expected a gate 'a OP b -> c'"));
+			};
+			let row_data = caps.iter().map(|m| m.unwrap().as_str()).collect_vec();
+			let input_a_id = row_data[1];
+			let op = row_data[2];
+			let input_b_id = row_data[3];
+			let output_id = row_data[4];
+
+			// create the gate
+			let gate = Rc::from(RefCell::from( Gate {
+				idx: gate_idx,
+				output_value: None,
+				//input_ids: [ input_a_id.to_string(), input_b_id.to_string() ],
+				output_id: output_id.to_string(),
+				op: Operation::from_str(&op),
+				role: GateRole::UNK,
+				n: None,
+			}));
+
+			// initialise associated wires
+			for input_id in [ input_a_id, input_b_id ].into_iter() {
+				if let Some(w) = circuit.get_wire_by_id(input_id) {
+					w.borrow_mut().output_gates.push(gate.clone());
+				} else {
+					circuit.wires.push(Rc::from(RefCell::from( Wire {
+						id: input_id.to_string(),
+						value: None,
+						output_gates: vec![gate.clone()],
+						role: 'U',
+						n: None,
+					})));
+				}
+			}
 
-	// perform calculation
-	circuit.run_calculation();
+			if let None = circuit.get_wire_by_id(output_id) {
+				circuit.wires.push( Rc::from(RefCell::from( Wire {
+					id: output_id.to_string(),
+					value: None,
+					output_gates: vec![],
+					role: 'U',
+					n: None,
+				} )));
+			}
 
-	// get the solution
-	// expected 55544677167336 for input24.txt
-	let p1soln = circuit.get_output();
-	println!("part 1 solution: {}", p1soln);
-	
-	// part 2
-	// see notes24.txt for digital logic
-	
-	// analyse gate types and wire types
+			circuit.gates.push(gate);
+			row += 1;
+			gate_idx += 1;
+		}
 
-	let gate_types = circuit.gates.iter().map(|g| g.borrow().op.to_string()).collect_vec();
-	let gate_counts = gate_types.iter().map(|k| (k,1)).into_group_map();
-	let s: String = itertools::Itertools::intersperse(gate_counts.iter().map(|(k,v)| format!("{}={}", k, v.iter().sum::<i32>())), ",".to_string()).collect();
-	println!("gate types: {}", s);
+		Ok(circuit)
+	}
+
+	fn part1(&self, circuit: &mut Self::Input) -> AocResult<String> {
+		// perform calculation
+		circuit.run_calculation();
+
+		// get the solution
+		// expected 55544677167336 for input24.txt
+		let Some(p1soln) = circuit.get_output() else {
+			return Err(AocError::new("part 1: some z wires were never given a value"));
+		};
+		info!("part 1 solution: {}", p1soln);
+		Ok(p1soln.to_string())
+	}
 
-	let wire_ids = circuit.wires.iter().map(|a| a.borrow().id.chars().map(|c| c).collect_vec()).collect_vec();
-	let non_input_wires = wire_ids.iter().enumerate().filter(|(_i,wid)| wid[0] != 'x' && wid[0] != 'y').collect_vec();
-	println!("input wires: {}", circuit.wires.len() - non_input_wires.len());
-	println!("non-input wires: {}", non_input_wires.len());
+	fn part2(&self, circuit: &mut Self::Input) -> AocResult<String> {
+		// see notes24.txt for digital logic
+	
+		// analyse gate types and wire types
+
+		let gate_types = circuit.gates.iter().map(|g| g.borrow().op.to_string()).collect_vec();
+		let gate_counts = gate_types.iter().map(|k| (k,1)).into_group_map();
+		let s: String = itertools::Itertools::intersperse(gate_counts.iter().map(|(k,v)| format!("{}={}", k, v.iter().sum::<i32>())), ",".to_string()).collect();
+		debug!("gate types: {}", s);
+
+		let wire_ids = circuit.wires.iter().map(|a| a.borrow().id.chars().map(|c| c).collect_vec()).collect_vec();
+		let non_input_wires = wire_ids.iter().enumerate().filter(|(_i,wid)| wid[0] != 'x' && wid[0] != 'y').collect_vec();
+		debug!("input wires: {}", circuit.wires.len() - non_input_wires.len());
+		debug!("non-input wires: {}", non_input_wires.len());
+
+		// input wires are all valid
+
+		let mut valid_wire_ids: Vec<String> = Vec::new();
+
+		for w in circuit.wires.iter() {
+			let c = w.borrow().id.chars().collect_vec()[0];
+			if c == 'x' || c == 'y' {
+				valid_wire_ids.push(w.borrow().id.clone());
+				w.borrow_mut().role = if c == 'x' {
+					'X'
+				} else {
+					'Y'
+				};
+				let n = w.borrow().id[1..=2].parse::<usize>().unwrap();
+				w.borrow_mut().n = Some(n);
+			}
+		}
 
-	// input wires are all valid
+		// XOR1 gates are identifiable from input wires
 
-	let mut valid_wire_ids: Vec<String> = Vec::new();
+		let xor1s = circuit.gates.iter()
+			.filter(|g| g.borrow().op == Operation::XOR &&
+				circuit.get_gate_input_ids(g.borrow().idx).iter().all(|wid| valid_wire_ids.iter().any(|id| id==wid)))
+			.map(|a| a.clone()).collect_vec();
 
-	for w in circuit.wires.iter() {
-		let c = w.borrow().id.chars().collect_vec()[0];
-		if c == 'x' || c == 'y' {
-			valid_wire_ids.push(w.borrow().id.clone());
-			w.borrow_mut().role = if c == 'x' {
-				'X'
+		xor1s.iter().for_each(|g| {
+			let input_ids = circuit.get_gate_input_ids(g.borrow().idx);
+			let n = input_ids[0][1..=2].parse::<usize>().unwrap();
+			let n2 = input_ids[1][1..=2].parse::<usize>().unwrap();
+			if n != n2 {
+				info!("ERROR: input ids do not match");
+			}
+			g.borrow_mut().role = if n == 0 {
+				GateRole::XOR
 			} else {
-				'Y'
+				GateRole::XOR1
 			};
-			let n = w.borrow().id[1..=2].parse::<usize>().unwrap();
-			w.borrow_mut().n = Some(n);
+			g.borrow_mut().n = Some(n);
+		});
+
+		if xor1s.len() != 45 {
+			info!("ERROR: Invalid number of XOR1 gates. Expected 45, got {}", xor1s.len());
+		} else {
+			// we can label the rest of the XOR gates
+			circuit.gates.iter().filter(|g| g.borrow().role == GateRole::UNK && g.borrow().op == Operation::XOR).for_each(|g| {
+				g.borrow_mut().role = GateRole::XOR2;
+			});
 		}
-	}
 
-	// XOR1 gates are identifiable from input wires
+		// AND1 gates are identifiable from input wires
 
-	let xor1s = circuit.gates.iter()
-		.filter(|g| g.borrow().op == Operation::XOR &&
-			circuit.get_gate_input_ids(g.borrow().idx).iter().all(|wid| valid_wire_ids.iter().any(|id| id==wid)))
-		.map(|a| a.clone()).collect_vec();
+		let and1s = circuit.gates.iter()
+			.filter(|g| g.borrow().op == Operation::AND &&
+				circuit.get_gate_input_ids(g.borrow().idx).iter().all(|wid| valid_wire_ids.iter().any(|id| id==wid)))
+			.map(|a| a.clone()).collect_vec();
 
-	xor1s.iter().for_each(|g| {
-		let input_ids = circuit.get_gate_input_ids(g.borrow().idx);
-		let n = input_ids[0][1..=2].parse::<usize>().unwrap();
-		let n2 = input_ids[1][1..=2].parse::<usize>().unwrap();
-		if n != n2 {
-			println!("ERROR: input ids do not match");
-		}
-		g.borrow_mut().role = if n == 0 {
-			GateRole::XOR
-		} else {
-			GateRole::XOR1
-		};
-		g.borrow_mut().n = Some(n);
-	});
-
-	if xor1s.len() != 45 {
-		println!("ERROR: Invalid number of XOR1 gates. Expected 45, got {}", xor1s.len());
-	} else {
-		// we can label the rest of the XOR gates
-		circuit.gates.iter().filter(|g| g.borrow().role == GateRole::UNK && g.borrow().op == Operation::XOR).for_each(|g| {
-			g.borrow_mut().role = GateRole::XOR2;
+		and1s.iter().for_each(|g| {
+			let input_ids = circuit.get_gate_input_ids(g.borrow().idx);
+			let n = input_ids[0][1..=2].parse::<usize>().unwrap();
+			let n2 = input_ids[1][1..=2].parse::<usize>().unwrap();
+			if n != n2 {
+				info!("ERROR: input ids do not match");
+			}
+			g.borrow_mut().role = if n == 0 {
+				GateRole::AND
+			} else {
+				GateRole::AND1
+			};
+			g.borrow_mut().n = Some(n);
 		});
-	}
-
-	// AND1 gates are identifiable from input wires
-
-	let and1s = circuit.gates.iter()
-		.filter(|g| g.borrow().op == Operation::AND &&
-			circuit.get_gate_input_ids(g.borrow().idx).iter().all(|wid| valid_wire_ids.iter().any(|id| id==wid)))
-		.map(|a| a.clone()).collect_vec();
 
-	and1s.iter().for_each(|g| {
-		let input_ids = circuit.get_gate_input_ids(g.borrow().idx);
-		let n = input_ids[0][1..=2].parse::<usize>().unwrap();
-		let n2 = input_ids[1][1..=2].parse::<usize>().unwrap();
-		if n != n2 {
-			println!("ERROR: input ids do not match");
-		}
-		g.borrow_mut().role = if n == 0 {
-			GateRole::AND
+		if and1s.len() != 45 {
+			info!("ERROR: Invalid number of AND1 gates. Expected 45, got {}", xor1s.len());
 		} else {
-			GateRole::AND1
-		};
-		g.borrow_mut().n = Some(n);
-	});
-
-	if and1s.len() != 45 {
-		println!("ERROR: Invalid number of AND1 gates. Expected 45, got {}", xor1s.len());
-	} else {
-		// we can label the rest of the AND gates
-		circuit.gates.iter().filter(|g| g.borrow().role == GateRole::UNK && g.borrow().op == Operation::AND).for_each(|g| {
-			g.borrow_mut().role = GateRole::AND2;
-		});
-	}
+			// we can label the rest of the AND gates
+			circuit.gates.iter().filter(|g| g.borrow().role == GateRole::UNK && g.borrow().op == Operation::AND).for_each(|g| {
+				g.borrow_mut().role = GateRole::AND2;
+			});
+		}
 
-	// All OR gate have role GateRole::OR
+		// All OR gate have role GateRole::OR
 
-	circuit.gates.iter().filter(|g| g.borrow().op == Operation::OR).for_each(|g| {
-		g.borrow_mut().role = GateRole::OR;
-	});
+		circuit.gates.iter().filter(|g| g.borrow().op == Operation::OR).for_each(|g| {
+			g.borrow_mut().role = GateRole::OR;
+		});
 
-	// All gates should have a role now
+		// All gates should have a role now
 
-	let unassigned = circuit.gates.iter().filter(|g| g.borrow().role == GateRole::UNK).collect_vec();
-	if unassigned.len() > 0 {
-		println!("ERROR: Some gates do not have a role!");
-		return (p1soln.to_string(), "unknown".to_string());
-	}
+		let unassigned = circuit.gates.iter().filter(|g| g.borrow().role == GateRole::UNK).collect_vec();
+		if unassigned.len() > 0 {
+			info!("ERROR: Some gates do not have a role!");
+			return Ok("unknown".to_string());
+		}
 
 
-	// find definitely invalid wires, by looking to see if gate output matches desired kind of gate output
-	println!("Finding invalid gate outputs...");
-	let mut dodgy_gates: Vec<Rc<RefCell<Gate>>> = vec![];
+		// find definitely invalid wires, by looking to see if gate output matches desired kind of gate output
+		info!("Finding invalid gate outputs...");
+		let mut dodgy_gates: Vec<Rc<RefCell<Gate>>> = vec![];
 
-	for g in circuit.gates.iter() {
-		let role = g.borrow().role;
-		let output_id = g.borrow().output_id.clone();
-		let mut is_dodgy = false;
-		match role {
-			GateRole::XOR => {
-				// test that output goes to z00
-				if g.borrow().output_id != "z00" {
-					is_dodgy = true;
-				}
-			},
-            GateRole::XOR1 => {
-				// test that output goes to XOR2 and AND2
-				let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
-				let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
-				if output_gate_roles != vec![GateRole::XOR2, GateRole::AND2] && output_gate_roles != vec![GateRole::AND2, GateRole::XOR2] {
-					is_dodgy = true;
-				}
-			},
-			GateRole::XOR2 => {
-				// test XOR2 outputs to Z
-				if &g.borrow().output_id[0..1] != "z" {
-					is_dodgy = true;
-				}
-			},
-			GateRole::AND => {
-				// check outputs to XOR2 and AND2
-				let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
-				let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
-				if output_gate_roles != vec![GateRole::XOR2, GateRole::AND2] && output_gate_roles != vec![GateRole::AND2, GateRole::XOR2] {
-					is_dodgy = true;
-				}
-			},
-			GateRole::AND1 => {
-				// check it outputs to OR gate
-				let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
-				let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
-				if output_gate_roles != vec![GateRole::OR] {
-					is_dodgy = true;
-				}
-			},
-			GateRole::AND2 => {
-				// output must go to an OR gate
-				let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
-				let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
-				if output_gate_roles != vec![GateRole::OR] {
-					is_dodgy = true;
-				}
-			},
-			GateRole::OR => {
-				// output must be either z45 or go to XOR2 and AND2
-				if output_id != "z45" {
+		for g in circuit.gates.iter() {
+			let role = g.borrow().role;
+			let output_id = g.borrow().output_id.clone();
+			let mut is_dodgy = false;
+			match role {
+				GateRole::XOR => {
+					// test that output goes to z00
+					if g.borrow().output_id != "z00" {
+						is_dodgy = true;
+					}
+				},
+	            GateRole::XOR1 => {
+					// test that output goes to XOR2 and AND2
 					let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
 					let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
 					if output_gate_roles != vec![GateRole::XOR2, GateRole::AND2] && output_gate_roles != vec![GateRole::AND2, GateRole::XOR2] {
 						is_dodgy = true;
 					}
-				}
-			},
-			GateRole::UNK => { panic!("shouldn't be able to reach here"); },
-		}
-		if is_dodgy {
-			dodgy_gates.push(g.clone());
-			println!("invalid output for gate {} at idx {} with output wire {}", g.borrow().to_string(), g.borrow().idx, output_id);
+				},
+				GateRole::XOR2 => {
+					// test XOR2 outputs to Z
+					if &g.borrow().output_id[0..1] != "z" {
+						is_dodgy = true;
+					}
+				},
+				GateRole::AND => {
+					// check outputs to XOR2 and AND2
+					let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
+					let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
+					if output_gate_roles != vec![GateRole::XOR2, GateRole::AND2] && output_gate_roles != vec![GateRole::AND2, GateRole::XOR2] {
+						is_dodgy = true;
+					}
+				},
+				GateRole::AND1 => {
+					// check it outputs to OR gate
+					let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
+					let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
+					if output_gate_roles != vec![GateRole::OR] {
+						is_dodgy = true;
+					}
+				},
+				GateRole::AND2 => {
+					// output must go to an OR gate
+					let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
+					let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
+					if output_gate_roles != vec![GateRole::OR] {
+						is_dodgy = true;
+					}
+				},
+				GateRole::OR => {
+					// output must be either z45 or go to XOR2 and AND2
+					if output_id != "z45" {
+						let output_gates = circuit.get_wire_by_id(&output_id).unwrap().borrow().output_gates.clone();
+						let output_gate_roles = output_gates.iter().map(|og| og.borrow().role.clone()).collect_vec();
+						if output_gate_roles != vec![GateRole::XOR2, GateRole::AND2] && output_gate_roles != vec![GateRole::AND2, GateRole::XOR2] {
+							is_dodgy = true;
+						}
+					}
+				},
+				GateRole::UNK => { panic!("shouldn't be able to reach here"); },
+			}
+			if is_dodgy {
+				dodgy_gates.push(g.clone());
+				debug!("invalid output for gate {} at idx {} with output wire {}", g.borrow().to_string(), g.borrow().idx, output_id);
+			}
 		}
-	}
 
-	println!("Invalid gate outputs found: {}", dodgy_gates.len());
+		info!("Invalid gate outputs found: {}", dodgy_gates.len());
 
-	let mut dodgy_ids = dodgy_gates.iter().map(|g| g.borrow().output_id.clone()).collect_vec();
-	dodgy_ids.sort();
-	let p2result = itertools::Itertools::intersperse(dodgy_ids.into_iter(), ",".to_string()).collect_vec();
-	let p2result_s: String = p2result.into_iter().collect();
-	println!("part 2 result: {}", p2result_s);
+		let mut dodgy_ids = dodgy_gates.iter().map(|g| g.borrow().output_id.clone()).collect_vec();
+		dodgy_ids.sort();
+		let p2result = itertools::Itertools::intersperse(dodgy_ids.into_iter(), ",".to_string()).collect_vec();
+		let p2result_s: String = p2result.into_iter().collect();
+		info!("part 2 result: {}", p2result_s);
 
-	(p1soln.to_string(), p2result_s)
-}
\ No newline at end of file
+		Ok(p2result_s)
+	}
+}
@@ -1,50 +1,85 @@
 use itertools::Itertools;
 //use std::collections::{*};
+use crate::solver::{Registry, Solver, Params};
+use crate::error::{*};
+use crate::grid::Grid;
+use crate::vector::Vector;
 
-pub fn day25(input: &String) -> (String, String) {
-	let lines = input.lines().collect_vec();
-	let mut idx: usize = 0;
-	let mut keys: Vec<[u8;5]> = vec![];
-	let mut locks: Vec<[u8;5]> = vec![];
-	while idx + 6 < lines.len() {
-		let mut deets: [u8; 5] = [0, 0, 0, 0, 0];
-		// lock
-		for i in 1..=5 {
-			for x in 0..=4 {
-				if &lines[idx+i][x..=x] == "#" {
-					deets[x] = deets[x] + 1;
+pub struct Input {
+	keys: Vec<[u8;5]>,
+	locks: Vec<[u8;5]>,
+}
+
+pub struct Day25;
+
+pub fn register(r: &mut Registry) {
+	r.add(Day25);
+}
+
+impl Solver for Day25 {
+	type Input = Input;
+
+	fn name(&self) -> &'static str { "day25" }
+	fn day(&self) -> usize { 25 }
+
+	fn parse(&self, input: &str, _params: &Params) -> AocResult<Self::Input> {
+		let lines = input.lines().collect_vec();
+		let mut idx: usize = 0;
+		let mut keys: Vec<[u8;5]> = vec![];
+		let mut locks: Vec<[u8;5]> = vec![];
+		while idx + 6 < lines.len() {
+			// each key or lock is 7 lines, 5 wide, of # and .
+			for y in idx..idx + 7 {
+				if lines[y].len() != 5 {
+					return Err(AocError::in_line(y, format!("unexpected line width {}, expected 5", lines[y].len())));
+				}
+				if let Some(x) = lines[y].bytes().position(|b| b != b'#' && b != b'.') {
+					return Err(AocError::at(y, x, "expected '#' or '.'"));
 				}
 			}
+			// the pin heights: the #s in each column, not counting the top and bottom rows
+			let grid = Grid::from_str(&lines[idx..idx + 7].join("\n"))?;
+			let pins = grid.crop(&Vector::new(0, 1), 5, 5);
+			let mut deets: [u8; 5] = [0, 0, 0, 0, 0];
+			for (x, col) in pins.cols().enumerate() {
+				deets[x] = col.filter(|&&b| b == b'#').count() as u8;
+			}
+			if grid.get_unchecked(&Vector::new(0, 0)) == b'#' {
+				locks.push(deets);
+				trace!("found lock: {:?}", deets);
+			} else {
+				keys.push(deets);
+				trace!("found key:  {:?}", deets);
+			}
+			idx += 8;
 		}
-		if &lines[idx][0..1] == "#" {
-			locks.push(deets);
-			//println!("found lock: {:?}", deets);
-		} else {
-			keys.push(deets);
-			//println!("found key:  {:?}", deets);
-		}
-		idx += 8;
+		Ok(Input { keys, locks })
 	}
 
-	let mut fits = 0;
-	for k in keys.iter() {
-		for l in locks.iter() {
-			let mut result: [u8; 5] = [0; 5];
-			let mut failed = false;
-			for i in 0..=4 {
-				result[i] = l[i] + k[i];
-				if result[i] > 5 {
-					failed = true;
+	fn part1(&self, input: &mut Self::Input) -> AocResult<String> {
+		let mut fits = 0;
+		for k in input.keys.iter() {
+			for l in input.locks.iter() {
+				let mut result: [u8; 5] = [0; 5];
+				let mut failed = false;
+				for i in 0..=4 {
+					result[i] = l[i] + k[i];
+					if result[i] > 5 {
+						failed = true;
+					}
+				}
+				if !failed {
+					fits += 1;
 				}
-			}
-			if !failed {
-				fits += 1;
 			}
 		}
-	}
 
-	println!("part 1 result (fits): {}", fits);
-
-	(fits.to_string(), "no result".to_string())
+		info!("part 1 result (fits): {}", fits);
+		Ok(fits.to_string())
+	}
 
-}
\ No newline at end of file
+	fn part2(&self, _input: &mut Self::Input) -> AocResult<String> {
+		// there is no part two on the last day
+		Ok("no result".to_string())
+	}
+}
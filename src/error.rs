@@ -0,0 +1,75 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// error.rs: AocError, for reporting problems with the puzzle input (and where they are)
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AocError {
+    pub line: Option<usize>,    // 1-based, as shown to the user
+    pub col: Option<usize>,     // 1-based, as shown to the user
+    pub msg: String,
+}
+
+pub type AocResult<T> = Result<T, AocError>;
+
+impl AocError {
+    pub fn new(msg: impl Into<String>) -> Self {
+        Self { line: None, col: None, msg: msg.into() }
+    }
+    // line_idx is 0-based, as from lines().enumerate()
+    pub fn in_line(line_idx: usize, msg: impl Into<String>) -> Self {
+        Self { line: Some(line_idx + 1), col: None, msg: msg.into() }
+    }
+    // line_idx and col_idx are 0-based, col_idx is a byte offset into the line (our inputs are ascii)
+    pub fn at(line_idx: usize, col_idx: usize, msg: impl Into<String>) -> Self {
+        Self { line: Some(line_idx + 1), col: Some(col_idx + 1), msg: msg.into() }
+    }
+    // find the line and column from a byte offset into the whole input, e.g. from a regex match
+    pub fn at_offset(input: &str, offset: usize, msg: impl Into<String>) -> Self {
+        let before = &input[..offset];
+        let line_idx = before.matches('\n').count();
+        let col_idx = offset - before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self::at(line_idx, col_idx, msg)
+    }
+    // add a line number to an error that doesn't have one, e.g. from a parser working on a single line
+    pub fn with_line(mut self, line_idx: usize) -> Self {
+        if self.line.is_none() {
+            self.line = Some(line_idx + 1);
+        }
+        self
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.col) {
+            (Some(l), Some(c)) => write!(f, "line {}, col {}: {}", l, c, self.msg),
+            (Some(l), None) => write!(f, "line {}: {}", l, self.msg),
+            _ => write!(f, "{}", self.msg),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+// parse a value (usually a number) found at line_idx, col_idx
+pub fn parse_at<T: FromStr>(s: &str, line_idx: usize, col_idx: usize) -> AocResult<T> {
+    s.parse::<T>().map_err(|_| AocError::at(line_idx, col_idx, format!("expected a number, found '{}'", s)))
+}
+
+// split a line by whitespace, returning each token with its (0-based) column
+pub fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace().map(move |t| (t.as_ptr() as usize - line.as_ptr() as usize, t))
+}
+
+// parse all the numbers found by a regex, e.g. r"(-?\d+)", keeping the offset of each one
+pub fn regex_nums<T: FromStr>(re: &regex::Regex, input: &str) -> AocResult<Vec<(usize, T)>> {
+    re.find_iter(input).map(|m| {
+        m.as_str().parse::<T>()
+            .map(|n| (m.start(), n))
+            .map_err(|_| AocError::at_offset(input, m.start(), format!("number out of range: '{}'", m.as_str())))
+    }).collect()
+}
@@ -1,10 +1,12 @@
 use crate::vector::Vector;
+use crate::error::{AocError,AocResult};
 use itertools::Itertools;
 
-pub struct Grid {
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T = u8> {
     pub w: i32,
     pub h: i32,
-    pub data: Vec<u8>,
+    pub data: Vec<T>,
 }
 
 pub const NDIR_U:  usize = 0;
@@ -16,91 +18,361 @@ pub const NDIR_DL: usize = 5;
 pub const NDIR_L:  usize = 6;
 pub const NDIR_UL: usize = 7;
 
-pub const NDIRS: [Vector; 8] = [ Vector(0,-1), Vector(1,-1), Vector(1,0), Vector(1,1),
-Vector(0,1), Vector(-1,1), Vector(-1,0), Vector(-1,-1) ];
+pub const NDIRS: [Vector; 8] = [ Vector::new(0,-1), Vector::new(1,-1), Vector::new(1,0), Vector::new(1,1),
+Vector::new(0,1), Vector::new(-1,1), Vector::new(-1,0), Vector::new(-1,-1) ];
 
-impl Grid {
-    pub fn new(w: i32, h: i32) -> Self {
+// a grid of any cell type, e.g. Grid<u32> for distances, Grid<bool> for visited flags
+// Grid<u8> (the default) is the text map case, with from_str() and to_string()
+impl<T: Clone> Grid<T> {
+    pub fn new_with(w: i32, h: i32, c: T) -> Self {
         Self {
             w,
             h,
-            data: vec![b'.'; w as usize * h as usize],
+            data: vec![c; w as usize * h as usize],
         }
     }
-    pub fn new_with(w: i32, h: i32, c: u8) -> Self {
-        Self {
-            w,
-            h,
-            data: vec![c as u8; w as usize * h as usize],
-        }
+    // a grid the same size as this one, filled with c
+    pub fn same_size_with<U: Clone>(&self, c: U) -> Grid<U> {
+        Grid::new_with(self.w, self.h, c)
     }
-    pub fn from_str(s: &str) -> Self {
-        let rows = s.lines().collect::<Vec<&str>>();
-        let data: Vec<u8> = rows.iter().map(|r| r.bytes().collect::<Vec<u8>>()).flatten().collect();
-        Self {
-            h: rows.len() as i32,
-            w: rows[0].len() as i32,
-            data,
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            w: self.w,
+            h: self.h,
+            data: self.data.iter().map(f).collect(),
         }
     }
+    fn idx(&self, xy: &Vector) -> usize {
+        xy.1 as usize * self.w as usize + xy.0 as usize
+    }
     pub fn has_xy(&self, xy: &Vector) -> bool {
         xy.0 >= 0 && xy.0 < self.w && xy.1 >= 0 && xy.1 < self.h
     }
-    pub fn get(&self, xy: &Vector) -> Option<u8> {
-        if xy.is_valid(&self) {
-            return Some(self.data[xy.1 as usize * self.w as usize + xy.0 as usize]);
+    pub fn get(&self, xy: &Vector) -> Option<T> {
+        self.get_ref(xy).cloned()
+    }
+    pub fn get_ref(&self, xy: &Vector) -> Option<&T> {
+        if self.has_xy(xy) {
+            return Some(&self.data[self.idx(xy)]);
         }
         None
     }
-    pub fn get_unchecked(&self, xy: &Vector) -> u8 {
-        self.data[xy.1 as usize * self.w as usize + xy.0 as usize]
+    pub fn get_mut(&mut self, xy: &Vector) -> Option<&mut T> {
+        if self.has_xy(xy) {
+            let i = self.idx(xy);
+            return Some(&mut self.data[i]);
+        }
+        None
     }
-    pub fn get_neighbours(&self, xy: &Vector) -> Vec<Option<u8>> {
+    pub fn get_unchecked(&self, xy: &Vector) -> T {
+        self.data[self.idx(xy)].clone()
+    }
+    pub fn get_neighbours(&self, xy: &Vector) -> Vec<Option<T>> {
         // U, UR, R, DR, D, DL, L, UL.
-        NDIRS.iter().map(|d| self.get(&xy.add(&d))).collect_vec()
+        NDIRS.iter().map(|d| self.get(&xy.add(d))).collect_vec()
     }
-    pub fn put(&mut self, xy: &Vector, value: u8) -> bool {
-        if xy.is_valid(&self) {
-            self.data[xy.1 as usize * self.w as usize + xy.0 as usize] = value;
+    pub fn put(&mut self, xy: &Vector, value: T) -> bool {
+        if self.has_xy(xy) {
+            let i = self.idx(xy);
+            self.data[i] = value;
             return true;
         }
         false
     }
-    pub fn replace_fn(&mut self, f: fn(u8) -> u8 ) {
-        for y in 0..self.h {
-            for x in 0..self.w {
-                let pt = &mut self.data[y as usize * self.w as usize + x as usize];
-                *pt = f(*pt);
-            }
+    pub fn replace_fn(&mut self, f: impl Fn(&T) -> T) {
+        for pt in self.data.iter_mut() {
+            *pt = f(pt);
         }
     }
-    pub fn put_unchecked(&mut self, xy: &Vector, value: u8) {
-        self.data[xy.1 as usize * self.w as usize + xy.0 as usize] = value;
-    }
-    pub fn put_unchecked_t(&mut self, xy: (isize,isize), value: u8) {
-        self.data[xy.1 as usize * self.w as usize + xy.0 as usize] = value;
+    pub fn put_unchecked(&mut self, xy: &Vector, value: T) {
+        let i = self.idx(xy);
+        self.data[i] = value;
     }
-    pub fn find(&self, value: u8) -> Vec<Vector> {
+    pub fn find_fn(&self, f: impl Fn(&T) -> bool) -> Vec<Vector> {
         let mut results: Vec<Vector> = Vec::new();
         for y in 0..self.h {
             for x in 0..self.w {
-                if self.data[y as usize * self.w as usize + x as usize] == value {
+                if f(&self.data[y as usize * self.w as usize + x as usize]) {
                     results.push(Vector::new(x,y));
                 }
             }
         }
         results
     }
-    pub fn find_fn(&self, f: fn(u8) -> bool) -> Vec<Vector> {
-        let mut results: Vec<Vector> = Vec::new();
-        for y in 0..self.h {
-            for x in 0..self.w {
-                if f(self.data[y as usize * self.w as usize + x as usize]) {
-                    results.push(Vector::new(x,y));
+    // all the cells reachable from start, where each step (from one cell's value to the next) is allowed by can_step
+    // e.g. |a, b| a == b for the region start is in, or |&a, &b| b == a + 1 for a path that climbs by one each step
+    pub fn flood_fill(&self, start: &Vector, conn: Connectivity, can_step: impl Fn(&T, &T) -> bool) -> Vec<Vector> {
+        self.fill(start, conn, can_step, &mut self.same_size_with(false))
+    }
+    // flood_fill, skipping (and marking) the cells already seen
+    fn fill(&self, start: &Vector, conn: Connectivity, can_step: impl Fn(&T, &T) -> bool, seen: &mut Grid<bool>) -> Vec<Vector> {
+        let mut cells: Vec<Vector> = vec![];
+        if !self.has_xy(start) {
+            return cells;
+        }
+        let mut stack: Vec<Vector> = vec![*start];
+        seen.put(start, true);
+        while let Some(xy) = stack.pop() {
+            let value = &self.data[self.idx(&xy)];
+            for d in conn.dirs() {
+                let nxy = xy.add(d);
+                if let Some(nvalue) = self.get_ref(&nxy) {
+                    if !seen.get_unchecked(&nxy) && can_step(value, nvalue) {
+                        seen.put_unchecked(&nxy, true);
+                        stack.push(nxy);
+                    }
                 }
             }
+            cells.push(xy);
         }
-        results
+        cells
+    }
+    // each cell's position and value, in row order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        self.data.iter().enumerate().map(|(i, v)| (Vector::new(i as i32 % self.w, i as i32 / self.w), v))
+    }
+}
+
+// transforms, each of which makes a new grid
+impl<T: Clone> Grid<T> {
+    // a w x h grid, with f(xy) in each cell
+    pub fn from_fn(w: i32, h: i32, f: impl Fn(Vector) -> T) -> Self {
+        Self {
+            w,
+            h,
+            data: (0..h).flat_map(|y| (0..w).map(move |x| Vector::new(x, y))).map(f).collect(),
+        }
+    }
+    // a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.h, self.w, |xy| self.get_unchecked(&Vector::new(xy.1, self.h - 1 - xy.0)))
+    }
+    // a quarter turn anticlockwise
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.h, self.w, |xy| self.get_unchecked(&Vector::new(self.w - 1 - xy.1, xy.0)))
+    }
+    pub fn rotate_180(&self) -> Self {
+        Self::from_fn(self.w, self.h, |xy| self.get_unchecked(&Vector::new(self.w - 1 - xy.0, self.h - 1 - xy.1)))
+    }
+    // turned clockwise by 90 degrees per quarter turn (negative for anticlockwise)
+    pub fn rotate(&self, quarter_turns: i32) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            1 => self.rotate_cw(),
+            2 => self.rotate_180(),
+            3 => self.rotate_ccw(),
+            _ => self.clone(),
+        }
+    }
+    // mirrored left to right
+    pub fn flip_h(&self) -> Self {
+        Self::from_fn(self.w, self.h, |xy| self.get_unchecked(&Vector::new(self.w - 1 - xy.0, xy.1)))
+    }
+    // mirrored top to bottom
+    pub fn flip_v(&self) -> Self {
+        Self::from_fn(self.w, self.h, |xy| self.get_unchecked(&Vector::new(xy.0, self.h - 1 - xy.1)))
+    }
+    // rows become columns
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.h, self.w, |xy| self.get_unchecked(&Vector::new(xy.1, xy.0)))
+    }
+    // a copy of the w x h rectangle at origin (clipped to the grid)
+    pub fn crop(&self, origin: &Vector, w: i32, h: i32) -> Self {
+        self.view(origin, w, h).to_grid()
+    }
+    // the w x h rectangle at origin (clipped to the grid), without copying it
+    pub fn view(&self, origin: &Vector, w: i32, h: i32) -> GridView<'_, T> {
+        let x0 = origin.0.clamp(0, self.w);
+        let y0 = origin.1.clamp(0, self.h);
+        let x1 = (origin.0 + w).clamp(x0, self.w);
+        let y1 = (origin.1 + h).clamp(y0, self.h);
+        GridView { grid: self, origin: Vector::new(x0, y0), w: x1 - x0, h: y1 - y0 }
+    }
+    // with a border n cells wide all around, of value
+    pub fn pad(&self, n: i32, value: T) -> Self {
+        Self::from_fn(self.w + 2 * n, self.h + 2 * n, |xy| self.get(&xy.add(&Vector::new(-n, -n))).unwrap_or_else(|| value.clone()))
+    }
+    // each cell becomes a block k cells wide and m high
+    pub fn scale(&self, k: i32, m: i32) -> Self {
+        self.scale_with(k, m, |c, _| c.clone())
+    }
+    // each cell becomes a block k cells wide and m high, of f(cell, position within the block)
+    // e.g. for day15, where a box O becomes [ then ]
+    pub fn scale_with(&self, k: i32, m: i32, f: impl Fn(&T, Vector) -> T) -> Self {
+        Self::from_fn(self.w * k, self.h * m, |xy| f(&self.data[self.idx(&Vector::new(xy.0 / k, xy.1 / m))], Vector::new(xy.0 % k, xy.1 % m)))
+    }
+    // nx copies across, and ny down
+    pub fn tile(&self, nx: i32, ny: i32) -> Self {
+        Self::from_fn(self.w * nx, self.h * ny, |xy| self.get_unchecked(&Vector::new(xy.0 % self.w, xy.1 % self.h)))
+    }
+}
+
+// lines of cells
+impl<T> Grid<T> {
+    pub fn row(&self, y: i32) -> impl Iterator<Item = &T> {
+        self.data[(y * self.w) as usize..((y + 1) * self.w) as usize].iter()
+    }
+    pub fn col(&self, x: i32) -> impl Iterator<Item = &T> {
+        self.data.iter().skip(x as usize).step_by(self.w.max(1) as usize)
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.w.max(1) as usize)
+    }
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.w).map(|x| self.col(x))
+    }
+    // the cells from start, stepping by dir until we leave the grid
+    pub fn line(&self, start: Vector, dir: Vector) -> impl Iterator<Item = (Vector, &T)> {
+        std::iter::successors(Some(start), move |xy| Some(xy.add(&dir)))
+            .take_while(|xy| xy.0 >= 0 && xy.0 < self.w && xy.1 >= 0 && xy.1 < self.h)
+            .map(|xy| (xy, &self.data[xy.1 as usize * self.w as usize + xy.0 as usize]))
+    }
+    // every line across the grid in a direction, e.g. NDIRS[NDIR_R] for the rows, or NDIRS[NDIR_DR] for the diagonals
+    // (each starts from the edge of the grid)
+    pub fn lines(&self, dir: Vector) -> impl Iterator<Item = impl Iterator<Item = (Vector, &T)>> {
+        (0..self.h).flat_map(move |y| (0..self.w).map(move |x| Vector::new(x, y)))
+            .filter(move |xy| { let p = xy.add(&Vector::new(-dir.0, -dir.1)); p.0 < 0 || p.0 >= self.w || p.1 < 0 || p.1 >= self.h })
+            .map(move |xy| self.line(xy, dir))
+    }
+    // the diagonals from top left to bottom right
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Vector, &T)>> {
+        self.lines(NDIRS[NDIR_DR])
+    }
+    // the diagonals from top right to bottom left
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = (Vector, &T)>> {
+        self.lines(NDIRS[NDIR_DL])
+    }
+}
+
+// a rectangle within a grid, from Grid::view() (positions are relative to its origin)
+#[derive(Clone, Copy, Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    pub origin: Vector,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl<T: Clone> GridView<'_, T> {
+    pub fn has_xy(&self, xy: &Vector) -> bool {
+        xy.0 >= 0 && xy.0 < self.w && xy.1 >= 0 && xy.1 < self.h
+    }
+    pub fn get(&self, xy: &Vector) -> Option<T> {
+        if self.has_xy(xy) {
+            return self.grid.get(&xy.add(&self.origin));
+        }
+        None
+    }
+    // each cell's (relative) position and value, in row order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        (0..self.h).flat_map(move |y| (0..self.w).map(move |x| Vector::new(x, y)))
+            .map(|xy| (xy, &self.grid.data[self.grid.idx(&xy.add(&self.origin))]))
+    }
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.w, self.h, |xy| self.grid.get_unchecked(&xy.add(&self.origin)))
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    pub fn find(&self, value: T) -> Vec<Vector> {
+        self.find_fn(|v| *v == value)
+    }
+
+    // label each connected region of equal cells, returning the label grid, and the regions (indexed by label)
+    pub fn label_regions(&self, conn: Connectivity) -> (Grid<usize>, Vec<Region<T>>) {
+        let mut labels: Grid<usize> = self.same_size_with(NO_LABEL);
+        let mut regions: Vec<Region<T>> = vec![];
+        let mut seen: Grid<bool> = self.same_size_with(false);
+        for (xy, value) in self.iter() {
+            if seen.get_unchecked(&xy) {
+                continue;
+            }
+            let label = regions.len();
+            let cells = self.fill(&xy, conn, |a, b| a == b, &mut seen);
+            let mut region = Region { label, value: value.clone(), start: xy, area: cells.len(), perimeter: 0, corners: 0, min: xy, max: xy };
+            for c in cells.iter() {
+                labels.put_unchecked(c, label);
+                region.min = Vector::new(region.min.0.min(c.0), region.min.1.min(c.1));
+                region.max = Vector::new(region.max.0.max(c.0), region.max.1.max(c.1));
+            }
+            regions.push(region);
+        }
+
+        // perimeter and corners are counted against the cells with a different label
+        for (xy, &label) in labels.iter() {
+            let same: Vec<bool> = NDIRS.iter().map(|d| labels.get(&xy.add(d)) == Some(label)).collect();
+            let region = &mut regions[label];
+            region.perimeter += [NDIR_U, NDIR_R, NDIR_D, NDIR_L].iter().filter(|&&d| !same[d]).count();
+            //  C!  this is a concave corner
+            //  !x
+            //
+            //  Cc
+            //  c!  this is a convex corner
+            for [a, b, c] in [[NDIR_R, NDIR_D, NDIR_DR], [NDIR_L, NDIR_D, NDIR_DL], [NDIR_R, NDIR_U, NDIR_UR], [NDIR_L, NDIR_U, NDIR_UL]] {
+                if (!same[a] && !same[b]) || (same[a] && same[b] && !same[c]) {
+                    region.corners += 1;
+                }
+            }
+        }
+        (labels, regions)
+    }
+}
+
+// which neighbours of a cell are connected to it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Connectivity {
+    Four,           // U, R, D, L
+    Eight,          // and the diagonals
+}
+
+impl Connectivity {
+    pub fn dirs(&self) -> impl Iterator<Item = &'static Vector> {
+        let step = match self {
+            Connectivity::Four => 2,
+            Connectivity::Eight => 1,
+        };
+        NDIRS.iter().step_by(step)
+    }
+}
+
+pub const NO_LABEL: usize = usize::MAX;
+
+// a connected region of equal cells, from label_regions()
+#[derive(Clone, Debug)]
+pub struct Region<T> {
+    pub label: usize,
+    pub value: T,
+    pub start: Vector,      // the first cell found (in row order)
+    pub area: usize,
+    pub perimeter: usize,   // the number of cell edges next to another region (or the edge of the grid)
+    pub corners: usize,     // which is also the number of straight sides
+    pub min: Vector,        // bounding box, inclusive
+    pub max: Vector,
+}
+
+impl<T> Region<T> {
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+}
+
+impl Grid {
+    pub fn new(w: i32, h: i32) -> Self {
+        Self::new_with(w, h, b'.')
+    }
+    pub fn from_str(s: &str) -> AocResult<Self> {
+        let rows = s.lines().collect::<Vec<&str>>();
+        if rows.is_empty() || rows[0].is_empty() {
+            return Err(AocError::new("empty grid"));
+        }
+        if let Some(y) = rows.iter().position(|r| r.len() != rows[0].len()) {
+            return Err(AocError::in_line(y, format!("unexpected line width {}, expected {}", rows[y].len(), rows[0].len())));
+        }
+        let data: Vec<u8> = rows.iter().map(|r| r.bytes().collect::<Vec<u8>>()).flatten().collect();
+        Ok(Self {
+            h: rows.len() as i32,
+            w: rows[0].len() as i32,
+            data,
+        })
     }
     pub fn to_string(&self) -> String {
         let mut s = String::new();
@@ -124,3 +396,4 @@ impl Grid {
 }
 
 
+
@@ -0,0 +1,241 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// image.rs: write grids, levels and bitmaps as images (PPM or PNG), with layers on top, e.g. for reports or snapshots
+//
+// Each cell is a square of pixels, coloured by a palette, and layers (the visited cells, the best path tiles, regions)
+// are blended over it. The PNG encoder is as simple as can be: the image data goes in stored (uncompressed)
+// deflate blocks, so the files are no smaller than a PPM, but anything can view them. Both are deterministic,
+// so the same picture always makes the same file.
+//
+// Images are only written when asked for, with --image-dir DIR, and then go in DIR (see save()).
+
+use std::sync::Mutex;
+use crate::error::{AocError, AocResult};
+use crate::grid::Grid;
+use crate::level::{Level, LevelBitmap};
+use crate::vector::Vector;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [128, 128, 128];
+pub const RED: Rgb = [220, 40, 40];
+pub const GREEN: Rgb = [40, 180, 60];
+pub const BLUE: Rgb = [50, 90, 220];
+pub const YELLOW: Rgb = [230, 200, 40];
+
+static DIR: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn set_dir(dir: &str) {
+    *DIR.lock().unwrap() = Some(dir.to_string());
+}
+
+pub fn enabled() -> bool {
+    DIR.lock().unwrap().is_some()
+}
+
+// the colour of each class of cell
+#[derive(Clone, Debug)]
+pub struct Palette {
+    classes: Vec<(u8, Rgb)>,
+    other: Rgb,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let p = Self { classes: vec![], other: WHITE };
+        let p = p.with(b"#", GREY).with(b".", BLACK).with(b"O[]", YELLOW);
+        p.with(b"@^>v<dS", GREEN).with(b"E", RED)
+    }
+}
+
+impl Palette {
+    pub fn with(mut self, cells: &[u8], colour: Rgb) -> Self {
+        self.classes.retain(|c| !cells.contains(&c.0));
+        self.classes.extend(cells.iter().map(|&c| (c, colour)));
+        self
+    }
+    pub fn colour(&self, cell: u8) -> Rgb {
+        self.classes.iter().find(|c| c.0 == cell).map(|c| c.1).unwrap_or(self.other)
+    }
+}
+
+// a colour for each region label, different enough from its neighbours' to tell them apart
+pub fn region_colour(label: usize) -> Rgb {
+    let x = (label as u32).wrapping_add(1).wrapping_mul(0x9e37_79b9);
+    [(x >> 24) as u8 | 0x40, (x >> 16) as u8 | 0x40, (x >> 8) as u8 | 0x40]
+}
+
+// one colour per cell (and scale x scale pixels per cell, when written out)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub w: usize,
+    pub h: usize,
+    pub scale: usize,
+    cells: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(w: usize, h: usize, colour: Rgb) -> Self {
+        Self { w, h, scale: 1, cells: vec![colour; w * h] }
+    }
+    // any kind of grid, with a colour for each value (e.g. region_colour for the labels from label_regions)
+    pub fn from_cells<T: Clone>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Self {
+        Self { w: grid.w as usize, h: grid.h as usize, scale: 1, cells: grid.data.iter().map(colour).collect() }
+    }
+    pub fn from_grid(grid: &Grid<u8>, palette: &Palette) -> Self {
+        Self::from_cells(grid, |&c| palette.colour(c))
+    }
+    pub fn from_level(level: &Level, palette: &Palette) -> Self {
+        let cells = level.rows().iter().flat_map(|l| l.bytes()).map(|c| palette.colour(c)).collect();
+        Self { w: level.w, h: level.h, scale: 1, cells }
+    }
+    pub fn from_bitmap(bmp: &LevelBitmap, set: Rgb, clear: Rgb) -> Self {
+        let mut img = Self::new(bmp.w, bmp.h, clear);
+        for y in 0..bmp.h {
+            for x in 0..bmp.w {
+                if bmp.get_v(Vector::new(x as i32, y as i32)) {
+                    img.cells[y * bmp.w + x] = set;
+                }
+            }
+        }
+        img
+    }
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+    pub fn get(&self, v: Vector) -> Option<Rgb> {
+        if v.0 < 0 || v.1 < 0 || v.0 as usize >= self.w || v.1 as usize >= self.h {
+            return None;
+        }
+        Some(self.cells[v.1 as usize * self.w + v.0 as usize])
+    }
+    // blend colour over some cells (alpha 255 covers them, 0 leaves them be), ignoring any outside the image
+    pub fn layer(&mut self, pts: impl IntoIterator<Item = Vector>, colour: Rgb, alpha: u8) {
+        for v in pts {
+            let Some(old) = self.get(v) else { continue };
+            let mix = |i: usize| ((colour[i] as u32 * alpha as u32 + old[i] as u32 * (255 - alpha as u32)) / 255) as u8;
+            self.cells[v.1 as usize * self.w + v.0 as usize] = [mix(0), mix(1), mix(2)];
+        }
+    }
+    // the pixels, row by row, as RGB bytes
+    fn pixel_rows(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        self.cells.chunks(self.w.max(1)).flat_map(move |row| {
+            let line: Vec<u8> = row.iter().flat_map(|c| std::iter::repeat_n(c, self.scale)).flatten().copied().collect();
+            std::iter::repeat_n(line, self.scale)
+        })
+    }
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.w * self.scale, self.h * self.scale).into_bytes();
+        self.pixel_rows().for_each(|row| out.extend(row));
+        out
+    }
+    pub fn to_png(&self) -> Vec<u8> {
+        // each row starts with its filter type (0 for none)
+        let mut raw: Vec<u8> = vec![];
+        for row in self.pixel_rows() {
+            raw.push(0);
+            raw.extend(row);
+        }
+        let mut ihdr = vec![];
+        ihdr.extend(((self.w * self.scale) as u32).to_be_bytes());
+        ihdr.extend(((self.h * self.scale) as u32).to_be_bytes());
+        ihdr.extend([8, 2, 0, 0, 0]);       // 8 bits per channel, RGB, deflate, no filter, no interlace
+
+        let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        png_chunk(&mut out, b"IHDR", &ihdr);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+    // write as PNG, or PPM if the path ends in .ppm
+    pub fn write(&self, path: &str) -> AocResult<()> {
+        let data = if path.ends_with(".ppm") { self.to_ppm() } else { self.to_png() };
+        std::fs::write(path, data).map_err(|e| AocError::new(format!("unable to write image {}: {}", path, e)))
+    }
+}
+
+// write the image as name.png in the --image-dir directory (if there is one)
+pub fn save(name: &str, img: &Image) -> AocResult<()> {
+    let Some(dir) = DIR.lock().unwrap().clone() else {
+        return Ok(());
+    };
+    let path = format!("{}/{}.png", dir, name);
+    img.write(&path)?;
+    debug!("wrote {}", path);
+    Ok(())
+}
+
+// numbers the frames of a simulation as they are saved: name-00000.png, name-00001.png, ...
+pub struct Frames {
+    name: String,
+    count: usize,
+}
+
+impl Frames {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), count: 0 }
+    }
+    pub fn save(&mut self, img: &Image) -> AocResult<()> {
+        save(&format!("{}-{:05}", self.name, self.count), img)?;
+        self.count += 1;
+        Ok(())
+    }
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+// a zlib stream of stored (uncompressed) deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(65535).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);      // an empty final block
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        out.push(if blocks.peek().is_none() { 1 } else { 0 });
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &x in chunk {
+            a += x as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
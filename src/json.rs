@@ -0,0 +1,74 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// json.rs: just enough JSON to write out the results for --format json
+
+// a JSON object, built up one field at a time (each value is kept as JSON text)
+#[derive(Clone, Debug, Default)]
+pub struct JsonObject {
+    fields: Vec<(String, String)>,
+}
+
+impl JsonObject {
+    pub fn new() -> Self {
+        Self { fields: vec![] }
+    }
+    fn field(mut self, key: &str, value: String) -> Self {
+        self.fields.push((key.to_string(), value));
+        self
+    }
+    pub fn str(self, key: &str, value: &str) -> Self {
+        self.field(key, escape(value))
+    }
+    pub fn opt_str(self, key: &str, value: Option<&str>) -> Self {
+        self.field(key, value.map(escape).unwrap_or("null".to_string()))
+    }
+    pub fn num(self, key: &str, value: f64) -> Self {
+        // JSON has no NaN or infinity
+        self.field(key, if value.is_finite() { format!("{}", value) } else { "null".to_string() })
+    }
+    pub fn opt_uint(self, key: &str, value: Option<usize>) -> Self {
+        self.field(key, value.map(|v| v.to_string()).unwrap_or("null".to_string()))
+    }
+    pub fn opt_bool(self, key: &str, value: Option<bool>) -> Self {
+        self.field(key, value.map(|v| v.to_string()).unwrap_or("null".to_string()))
+    }
+    pub fn obj(self, key: &str, value: JsonObject) -> Self {
+        self.field(key, value.to_string())
+    }
+    pub fn opt_obj(self, key: &str, value: Option<JsonObject>) -> Self {
+        self.field(key, value.map(|v| v.to_string()).unwrap_or("null".to_string()))
+    }
+}
+
+impl std::fmt::Display for JsonObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (i, (k, v)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}:{}", escape(k), v)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+// a JSON string, with quotes
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
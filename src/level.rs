@@ -3,51 +3,112 @@
 //
 // level.rs: store level data and perform basic operations
 
-use crate::path2::Move2;
-use std::convert::TryInto;
-use std::collections::BTreeSet;
+use crate::path2::{Move2,ALLMOVES2};
+use std::collections::{BTreeMap,BTreeSet};
 use std::string::String;
 use crate::vector::{Vector,VectorSm};
 use crate::obj::Obj;
+use crate::error::AocError;
+use crate::unionfind::UnionFind;
 
+// a bitset the size of the level (any size), with each row stored as whole u64 words
+// bits past the width of a row are always kept clear, so counts and row operations can work a word at a time
 #[derive(Clone,PartialEq)]
 pub struct LevelBitmap {
-	pub w: u8,
-	pub h: u8,
-	pub bitmap: Vec<[u64;3]>,
+	pub w: usize,
+	pub h: usize,
+	stride: usize,				// u64 words per row
+	pub bitmap: Vec<u64>,
 }
 
 impl LevelBitmap {
 	pub fn new(w: usize, h: usize) -> LevelBitmap {
+		let stride = w.div_ceil(64);
 		Self {
-			w: w.try_into().unwrap(),
-			h: h.try_into().unwrap(),
-			bitmap: vec![[0;3]; h],			// level is up to 141 chars wide
+			w,
+			h,
+			stride,
+			bitmap: vec![0; stride * h],
 		}
 	}
+	fn word_bit(&self, v: Vector) -> (usize, u64) {
+		// checked in release builds too, as a bit past the width would land in the padding, or the next row
+		assert!(v.0 >= 0 && (v.0 as usize) < self.w && v.1 >= 0 && (v.1 as usize) < self.h, "point outside the bitmap");
+		let x = v.0 as usize;
+		(v.1 as usize * self.stride + x / 64, 1 << (x % 64))
+	}
 	pub fn set_v(&mut self, v: Vector) {
-		let y = v.1 as usize;
-		let xn = v.0 as usize / 64;
-		let xr = v.0 as usize % 64;
-		self.bitmap[y][xn] |= 1 << xr;
+		let (i, bit) = self.word_bit(v);
+		self.bitmap[i] |= bit;
 	}
 	pub fn clear_v(&mut self, v: Vector) {
-		let y = v.1 as usize;
-		let xn = v.0 as usize/ 64;
-		let xr = v.0 as usize % 64;
-		self.bitmap[y][xn] &= !(1 << xr);
+		let (i, bit) = self.word_bit(v);
+		self.bitmap[i] &= !bit;
 	}
 	pub fn get_v(&self, v: Vector) -> bool {
-		let y = v.1 as usize;
-		let xn = v.0 as usize / 64;
-		let xr = v.0 as usize % 64;
-		self.bitmap[y][xn] & (1 << xr) != 0
+		let (i, bit) = self.word_bit(v);
+		self.bitmap[i] & bit != 0
+	}
+	pub fn row(&self, y: usize) -> &[u64] {
+		&self.bitmap[y * self.stride..(y + 1) * self.stride]
+	}
+	// set the bits that are set in other (which must be the same size)
+	pub fn union_with(&mut self, other: &LevelBitmap) {
+		assert!(self.w == other.w && self.h == other.h, "bitmaps differ in size");
+		self.bitmap.iter_mut().zip(other.bitmap.iter()).for_each(|(a, b)| *a |= b);
+	}
+	// clear the bits that aren't set in other (which must be the same size)
+	pub fn intersect_with(&mut self, other: &LevelBitmap) {
+		assert!(self.w == other.w && self.h == other.h, "bitmaps differ in size");
+		self.bitmap.iter_mut().zip(other.bitmap.iter()).for_each(|(a, b)| *a &= b);
+	}
+	pub fn count(&self) -> usize {
+		self.bitmap.iter().map(|b| b.count_ones() as usize).sum()
+	}
+	pub fn count_row(&self, y: usize) -> usize {
+		self.row(y).iter().map(|b| b.count_ones() as usize).sum()
+	}
+	// move every bit by dx columns and dy rows, dropping those that go past the edges
+	pub fn shift(&mut self, dx: i32, dy: i32) {
+		let mut shifted = LevelBitmap::new(self.w, self.h);
+		for y in 0..self.h {
+			let ny = y as i64 + dy as i64;
+			if ny < 0 || ny >= self.h as i64 {
+				continue;
+			}
+			let src = self.row(y);
+			let dst = &mut shifted.bitmap[ny as usize * self.stride..(ny as usize + 1) * self.stride];
+			let (words, bits) = ((dx.unsigned_abs() / 64) as usize, dx.unsigned_abs() % 64);
+			for (i, d) in dst.iter_mut().enumerate() {
+				// the word (and the one next to it) that ends up at i
+				let (a, b) = if dx >= 0 {
+					(i.checked_sub(words), i.checked_sub(words + 1))
+				} else {
+					(Some(i + words).filter(|&j| j < self.stride), Some(i + words + 1).filter(|&j| j < self.stride))
+				};
+				let a = a.map(|j| src[j]).unwrap_or(0);
+				let b = b.map(|j| src[j]).unwrap_or(0);
+				*d = match (dx >= 0, bits) {
+					(_, 0) => a,
+					(true, n) => (a << n) | (b >> (64 - n)),
+					(false, n) => (a >> n) | (b << (64 - n)),
+				};
+			}
+		}
+		// clear anything shifted past the width
+		if !self.w.is_multiple_of(64) {
+			let mask = (1u64 << (self.w % 64)) - 1;
+			for y in 0..self.h {
+				shifted.bitmap[y * self.stride + self.stride - 1] &= mask;
+			}
+		}
+		*self = shifted;
 	}
 	pub fn to_string(&self) -> String {
 		let mut s = String::new();
 		for y in 0..self.h {
 			for x in 0..self.w {
-				if self.get_v(Vector(x.into(),y.into())) {
+				if self.get_v(Vector::new(x as i32, y as i32)) {
 					s.push('#');
 				} else {
 					s.push('.');
@@ -59,16 +120,76 @@ impl LevelBitmap {
 	}
 }
 
+// what a character in a level stands for
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Cell {
+	Space,
+	Wall,
+	Start,
+	Goal,
+	Terrain,		// open space of some other kind (see cost.rs)
+	Deer,			// open space with the deer in it (an object, not a start)
+}
+
+// how to read a level: which character stands for what (all ASCII), and whether to strip the outer wall
+// after the grid, a blank line can be followed by metadata lines, such as 'title: easy-peasy-6'
+#[derive(Clone,Debug,PartialEq)]
+pub struct LevelFormat {
+	legend: Vec<(char, Cell)>,		// the first character for a kind of cell is the one written out
+	pub strip_border: bool,
+}
+
+impl Default for LevelFormat {
+	fn default() -> Self {
+		Self {
+			legend: vec![('.', Cell::Space), ('#', Cell::Wall), ('S', Cell::Start), ('E', Cell::Goal), ('d', Cell::Deer)],
+			strip_border: false,
+		}
+	}
+}
+
+impl LevelFormat {
+	// c stands for cell (replacing whatever it stood for before), and is now the one written out for it
+	pub fn with(mut self, c: char, cell: Cell) -> Self {
+		assert!(c.is_ascii() && c != '\n', "level characters must be ASCII");
+		self.legend.retain(|l| l.0 != c);
+		self.legend.insert(0, (c, cell));
+		self
+	}
+	pub fn with_terrain(self, chars: &str) -> Self {
+		chars.chars().fold(self, |f, c| f.with(c, Cell::Terrain))
+	}
+	pub fn with_border_stripped(mut self) -> Self {
+		self.strip_border = true;
+		self
+	}
+	pub fn cell(&self, c: char) -> Option<Cell> {
+		self.legend.iter().find(|l| l.0 == c).map(|l| l.1)
+	}
+	pub fn char_for(&self, cell: Cell) -> Option<char> {
+		self.legend.iter().find(|l| l.1 == cell).map(|l| l.0)
+	}
+}
+
+// the cells of a w x h level, without the outermost rows and columns
+fn inner_cells<T: Clone>(v: &[T], w: usize, h: usize) -> Vec<T> {
+	(1..h-1).flat_map(|y| v[y*w+1..y*w+w-1].iter().cloned()).collect()
+}
 
 #[derive(Clone,PartialEq)] //,PartialOrd
 pub struct Level {
-	pub w: u16,
-	pub h: u16,
+	pub w: usize,
+	pub h: usize,
 	pub deer_pos: Vector,
 	pub deer_dir: Move2,
-	pub end_pos: Vector,
-	pub start_pos: Vector,
+	pub end_pos: Vector,			// the first of the goals
+	pub start_pos: Vector,			// the first of the starts
+	pub starts: Vec<Vector>,
+	pub goals: Vec<Vector>,
+	pub meta: Vec<(String, String)>,	// the metadata lines (key: value), in order
+	format: LevelFormat,
 	data: Vec::<Obj>,
+	cells: Vec::<u8>,			// the character each cell was read from (the space character for starts and goals)
 	wall_pts: BTreeSet::<Vector>,
 	pub(crate) wall_bmp: LevelBitmap,
 }
@@ -83,10 +204,10 @@ impl Level {
 		if self.deer_pos == *pt {
 			return Obj::Deer;
 		}
-		self.data[(pt.0 as usize) + (pt.1 as usize) * (self.w as usize)]
+		self.data[(pt.0 as usize) + (pt.1 as usize) * self.w]
 	}
 	pub fn set_obj_at_pt(&mut self, pt: &Vector, obj: Obj) {
-		self.data[(pt.0 as usize) + (pt.1 as usize) * (self.w as usize)] = obj;
+		self.data[(pt.0 as usize) + (pt.1 as usize) * self.w] = obj;
 	}
 	pub fn get_obj_at_pt_checked(&self, pt: &Vector) -> Obj {
 		if pt.0 < 0 || pt.0 >= self.w as i32 || pt.1 < 0 || pt.1 >= self.h as i32 {
@@ -106,96 +227,118 @@ impl Level {
 		// deer should be in finish position
 		self.deer_pos == self.end_pos
 	}
-	pub fn from_str(level_str: &str) -> Result<Level, &str> {
-		let mut h: u16 = 0;
-		let mut w: u16 = 0;
+	pub fn from_str(level_str: &str) -> Result<Level, AocError> {
+		Self::parse(level_str, &LevelFormat::default())
+	}
+	pub fn parse(level_str: &str, format: &LevelFormat) -> Result<Level, AocError> {
+		let mut h: usize = 0;
+		let mut w: usize = 0;
 		let mut data = Vec::<Obj>::with_capacity(128);
-		let mut start_pos: Option<Vector> = None;
-		let mut end_pos: Option<Vector> = None;
-	
-		for (count, line) in level_str.lines().enumerate() {
-			let txt = line;
+		let mut cells = Vec::<u8>::with_capacity(128);
+		let mut starts = Vec::<Vector>::new();
+		let mut goals = Vec::<Vector>::new();
+		let mut meta = Vec::<(String, String)>::new();
+		let space = format.char_for(Cell::Space).unwrap_or('.') as u8;
+
+		let mut lines = level_str.lines().enumerate();
+		for (count, txt) in lines.by_ref() {
+			if txt.is_empty() {
+				break;		// the metadata (if any) follows
+			}
 			if count == 0 {
 				// read in length
-				w = txt.len() as u16;			
+				w = txt.chars().count();
 			}
 			// check length equal to w
-			if txt.len() == w as usize {
-				// split line into characters
-				for (i,c) in txt.char_indices() {		// chars() is iterator
-					if c == 'S' {
-						// found start deer_pos
-						if start_pos.is_none() {
-							start_pos = Some(Vector(i.try_into().unwrap(),h.try_into().unwrap()));
-						} else {
-							return Err("More than one start found!");
-						}
-						data.push(Obj::Space);
-					} else if c == 'E' {
-						// found end_pos
-						if end_pos.is_none() {
-							end_pos = Some(Vector(i.try_into().unwrap(),h.try_into().unwrap()));
-						} else {
-							return Err("More than one end found!");
-						}
-						data.push(Obj::Space);
-					} else {
-						data.push(Obj::from_char(&c));
-					}
-				}
-				h += 1;
-			} else {
-				panic!("unexpected line width");
+			if txt.chars().count() != w {
+				return Err(AocError::in_line(count, format!("unexpected line width {}, expected {}", txt.chars().count(), w)));
 			}
+			for (i,c) in txt.chars().enumerate() {
+				let (obj, cell) = match format.cell(c) {
+					Some(Cell::Start) => {
+						starts.push(Vector::new(i as i32, h as i32));
+						(Obj::Space, space)
+					},
+					Some(Cell::Goal) => {
+						goals.push(Vector::new(i as i32, h as i32));
+						(Obj::Space, space)
+					},
+					Some(Cell::Wall) => (Obj::Wall, c as u8),
+					Some(Cell::Space) | Some(Cell::Terrain) => (Obj::Space, c as u8),
+					Some(Cell::Deer) => (Obj::Deer, c as u8),
+					None => return Err(AocError::at(count, i, format!("unexpected character '{}'", c))),
+				};
+				data.push(obj);
+				cells.push(cell);
+			}
+			h += 1;
 		}
-
-		// remove the borders
-		// let mut tdata = Vec::<Obj>::new();
-		// for y in 1..(h-1) as usize {
-		// 	for x in 1..(w-1) as usize {
-		// 		tdata.push(data[y*w as usize+x]);
-		// 	}
-		// }
-		// let data = tdata;
-
-		// w -= 2;
-		// h -= 2;
-		if start_pos.is_none() || end_pos.is_none() {
-			return Err("Start and/or end not found in level!");
+		for (count, txt) in lines {
+			if txt.trim().is_empty() {
+				continue;
+			}
+			let Some((k, v)) = txt.split_once(':') else {
+				return Err(AocError::in_line(count, "expected metadata (key: value)"));
+			};
+			meta.push((k.trim().to_string(), v.trim().to_string()));
 		}
-		let	end_pos = end_pos.unwrap();
-		let start_pos = start_pos.unwrap();
 
+		if starts.is_empty() || goals.is_empty() {
+			return Err(AocError::new("Start and/or end not found in level!"));
+		}
 		if w < 3 || h < 3 {
-			//println!("Dimensions: {} x {}", w, h);
-			return Err("Width and Height must be at least 3!");
+			trace!("Dimensions: {} x {}", w, h);
+			return Err(AocError::new("Width and Height must be at least 3!"));
+		}
+
+		// remove the borders, which must be all wall (outside the level counts as wall anyway)
+		if format.strip_border {
+			for y in 0..h {
+				for x in 0..w {
+					if (x == 0 || y == 0 || x == w - 1 || y == h - 1) && data[y*w+x] != Obj::Wall {
+						return Err(AocError::at(y, x, "can't strip the border, as it isn't all wall"));
+					}
+				}
+			}
+			data = inner_cells(&data, w, h);
+			cells = inner_cells(&cells, w, h);
+			for p in starts.iter_mut().chain(goals.iter_mut()) {
+				*p = p.add(&Vector::new(-1, -1));
+			}
+			w -= 2;
+			h -= 2;
 		}
-		// if w > 127 || h > 127 || w * h > 256 {
-		// 	//println!("Dimensions: {} x {}", w, h);
-		// 	return Err("Level too big! Maximum width 127. Maximum height 127. Maximum width * height 256.");
-		// }
 
 		let mut level = Level {
 			w: w,
 			h: h,
-			deer_pos: start_pos,
+			deer_pos: starts[0],
 			deer_dir: Move2::Right,
-			start_pos: start_pos,
-			end_pos: end_pos,
+			start_pos: starts[0],
+			end_pos: goals[0],
+			starts,
+			goals,
+			meta,
+			format: format.clone(),
 			wall_pts: BTreeSet::new(),
-			wall_bmp: LevelBitmap::new(w.into(),h.into()),
+			wall_bmp: LevelBitmap::new(w, h),
 			data: data,
+			cells,
 		};
 		Self::init_level(&mut level);
 		return Ok(level);
 	}
+	// the value of a metadata line, e.g. meta_value("title")
+	pub fn meta_value(&self, key: &str) -> Option<&str> {
+		self.meta.iter().find(|m| m.0 == key).map(|m| m.1.as_str())
+	}
 	pub fn init_level(&mut self) {
 		// set up wall_pts and wall_bitmap
-		for y in 0..self.h {
-			for x in 0..self.w {
-				if self.get_obj_at_pt(&Vector(x.into(),y.into())) == Obj::Wall {
-					self.wall_pts.insert(Vector(x.into(),y.into()));
-					self.wall_bmp.set_v(Vector(x.into(), y.into()));
+		for y in 0..self.h as i32 {
+			for x in 0..self.w as i32 {
+				if self.get_obj_at_pt(&Vector::new(x,y)) == Obj::Wall {
+					self.wall_pts.insert(Vector::new(x,y));
+					self.wall_bmp.set_v(Vector::new(x,y));
 				}
 			}
 		}
@@ -236,21 +379,78 @@ impl Level {
 		( v.0 | v.1 | (self.w as i32 - v.0 - 1) | (self.h as i32 - v.1 - 1)  ) >= 0
 	}
 	pub fn vector_in_bounds8(&self, v: &VectorSm) -> bool {
-		v.0 >= 0 && v.0 < (self.w as i8) && v.1 >= 0 && v.1 < (self.h as i8)
+		v.0 >= 0 && (v.0 as usize) < self.w && v.1 >= 0 && (v.1 as usize) < self.h
+	}
+	// each row of cells, as characters in the format the level was read with (without any stripped border)
+	pub fn rows(&self) -> Vec<String> {
+		let start = self.format.char_for(Cell::Start).unwrap_or('S');
+		let goal = self.format.char_for(Cell::Goal).unwrap_or('E');
+		(0..self.h as i32).map(|y| (0..self.w as i32).map(|x| {
+			let v = Vector::new(x,y);
+			if self.starts.contains(&v) {
+				start
+			} else if self.goals.contains(&v) {
+				goal
+			} else {
+				self.cell_at(v) as char
+			}
+		}).collect()).collect()
 	}
+	// in the format the level was read with, so it can be read back the same (with the border put back, if it was stripped)
 	pub fn to_string(&self) -> String {
+		let mut rows = self.rows();
+		if self.format.strip_border {
+			let wall = self.format.char_for(Cell::Wall).unwrap_or('#');
+			let edge = wall.to_string().repeat(self.w + 2);
+			rows = std::iter::once(edge.clone()).chain(rows.into_iter().map(|r| format!("{}{}{}", wall, r, wall))).chain(std::iter::once(edge)).collect();
+		}
 		let mut s = String::new();
-		for y in 0..self.h as i32 {
-			for x in 0..self.w as i32 {
-				s += &self.get_obj_at_pt(&Vector(x,y)).to_char().to_string();
-			}
+		for row in rows {
+			s += &row;
 			s += "\n";
 		}
+		if !self.meta.is_empty() {
+			s += "\n";
+			for (k, v) in self.meta.iter() {
+				s += &format!("{}: {}\n", k, v);
+			}
+		}
 		s
 	}
+	// the kind of cell at v, e.g. b'#' for a wall, or a terrain character
+	pub fn cell_at(&self, v: Vector) -> u8 {
+		self.cells[(v.0 as usize) + (v.1 as usize) * self.w]
+	}
 	pub fn has_space_at(&self, v: Vector) -> bool {
 		!(!self.vector_in_bounds(&v) || self.wall_bmp.get_v(v))
 	}
+	// given walls that will be added one at a time, the index of the first one that cuts the starts off from the goals
+	// (None if a start and a goal stay connected, and an error if they weren't connected to begin with)
+	// we add them all, then remove them in reverse order, until the start and end are connected again
+	pub fn first_blocking_wall(&self, walls: &[Vector]) -> Result<Option<usize>, AocError> {
+		let mut level = self.clone();
+		let mut first_idx: BTreeMap<Vector,usize> = BTreeMap::new();
+		for (i, &v) in walls.iter().enumerate() {
+			if self.has_space_at(v) {
+				first_idx.entry(v).or_insert(i);
+				level.wall_bmp.set_v(v);
+			}
+		}
+		let mut regions = LevelRegions::new(&level);
+		let connected = |r: &mut LevelRegions| self.starts.iter().any(|&s| self.goals.iter().any(|&g| r.connected(s, g)));
+		if connected(&mut regions) {
+			return Ok(None);
+		}
+		for (i, &v) in walls.iter().enumerate().rev() {
+			if first_idx.get(&v) == Some(&i) {
+				regions.open(v);
+				if connected(&mut regions) {
+					return Ok(Some(i));
+				}
+			}
+		}
+		Err(AocError::new("the starts and goals aren't connected, even before any of the walls are added"))
+	}
 	pub fn get_path_pts(&self, path: &Vec<Move2>) -> Vec<Vector> {
 		let mut pos = self.start_pos.clone();
 		let mut vecs = Vec::<Vector>::new();
@@ -263,3 +463,94 @@ impl Level {
 	}
 }
 
+// which open (non-wall) cells of a level are connected to each other, as walls are removed
+// this answers 'what if' questions, such as whether the start would still reach the end, without a search
+pub struct LevelRegions {
+	w: usize,
+	open: LevelBitmap,
+	uf: UnionFind,
+}
+
+impl LevelRegions {
+	pub fn new(level: &Level) -> Self {
+		let mut regions = Self {
+			w: level.w,
+			open: LevelBitmap::new(level.w, level.h),
+			uf: UnionFind::new(level.w * level.h),
+		};
+		for y in 0..level.h as i32 {
+			for x in 0..level.w as i32 {
+				if level.has_space_at(Vector::new(x,y)) {
+					regions.open(Vector::new(x,y));
+				}
+			}
+		}
+		regions
+	}
+	fn idx(&self, v: Vector) -> usize {
+		v.1 as usize * self.w + v.0 as usize
+	}
+	fn in_bounds(&self, v: Vector) -> bool {
+		v.0 >= 0 && (v.0 as usize) < self.open.w && v.1 >= 0 && (v.1 as usize) < self.open.h
+	}
+	// remove the wall at v (if any), joining it to the open cells next to it
+	pub fn open(&mut self, v: Vector) {
+		self.open.set_v(v);
+		for m in ALLMOVES2 {
+			let n = v.apply_dir(&m);
+			if self.in_bounds(n) && self.open.get_v(n) {
+				self.uf.union(self.idx(v), self.idx(n));
+			}
+		}
+	}
+	pub fn is_open(&self, v: Vector) -> bool {
+		self.in_bounds(v) && self.open.get_v(v)
+	}
+	pub fn connected(&mut self, a: Vector, b: Vector) -> bool {
+		self.is_open(a) && self.is_open(b) && self.uf.same(self.idx(a), self.idx(b))
+	}
+	// the number of open cells connected to v (including v)
+	pub fn region_size(&mut self, v: Vector) -> usize {
+		if !self.is_open(v) {
+			return 0;
+		}
+		self.uf.size_of(self.idx(v))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// a level read back from to_string() is the same level
+	fn round_trip(text: &str, format: &LevelFormat) -> Level {
+		let level = Level::parse(text, format).expect("a valid level");
+		assert_eq!(level.to_string(), text);
+		assert!(Level::parse(&level.to_string(), format).expect("a valid level") == level);
+		level
+	}
+
+	#[test]
+	fn round_trip_default_legend_and_metadata() {
+		let level = round_trip("#####\n#S.d#\n#..E#\n#####\n\ntitle: easy-peasy-6\nauthor: me\n", &LevelFormat::default());
+		assert_eq!(level.starts, vec![Vector::new(1, 1)]);
+		assert_eq!(level.meta_value("title"), Some("easy-peasy-6"));
+		assert_eq!(level.meta_value("none"), None);
+	}
+
+	#[test]
+	fn round_trip_custom_legend() {
+		let format = LevelFormat::default().with('o', Cell::Start).with('x', Cell::Goal).with('%', Cell::Wall).with_terrain("~^");
+		let level = round_trip("%%%%%\n%o~^%\n%.~x%\n%%%%%\n", &format);
+		assert_eq!((level.starts.clone(), level.goals.clone()), (vec![Vector::new(1, 1)], vec![Vector::new(3, 2)]));
+		assert_eq!(level.cell_at(Vector::new(2, 1)), b'~');
+	}
+
+	#[test]
+	fn round_trip_stripped_border() {
+		let level = round_trip("#####\n#S..#\n#.#E#\n#####\n\ntitle: bordered\n", &LevelFormat::default().with_border_stripped());
+		assert_eq!((level.w, level.h), (3, 2));
+		assert_eq!((level.starts.clone(), level.goals.clone()), (vec![Vector::new(0, 0)], vec![Vector::new(2, 1)]));
+		assert!(!level.has_space_at(Vector::new(1, 1)));
+	}
+}
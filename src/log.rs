@@ -0,0 +1,158 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// log.rs: where the solvers' diagnostic output, and the runner's own messages, go
+//
+// Solvers print their progress with info!() rather than println!(), so that when the results are wanted
+// in a machine readable format (--format json), the diagnostics can go to stderr, or nowhere.
+// More detail is available with debug!() and trace!(), which are shown with -v and -vv (and -q shows none).
+// The runner prints its banners with say!(), which goes to stdout (or stderr, when stdout is for JSON).
+//
+// When days run in parallel (--jobs N), each one's output is buffered on its own thread, and printed
+// once it is done, so the output from different days doesn't interleave.
+
+use std::cell::RefCell;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sink {
+    Stdout,
+    Stderr,
+    Off,
+}
+
+impl Sink {
+    fn from_u8(n: u8) -> Self {
+        match n {
+            0 => Sink::Stdout,
+            1 => Sink::Stderr,
+            _ => Sink::Off,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Level {
+    Quiet,
+    Info,
+    Debug,
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+static SINK: AtomicU8 = AtomicU8::new(0);       // for the solvers' diagnostics
+static OUT: AtomicU8 = AtomicU8::new(0);        // for the runner's messages
+
+thread_local! {
+    static BUFFER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Quiet,
+        1 => Level::Info,
+        2 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+pub fn set_sink(sink: Sink) {
+    SINK.store(sink as u8, Ordering::Relaxed);
+}
+
+pub fn sink() -> Sink {
+    Sink::from_u8(SINK.load(Ordering::Relaxed))
+}
+
+pub fn set_out(sink: Sink) {
+    OUT.store(sink as u8, Ordering::Relaxed);
+}
+
+pub fn out() -> Sink {
+    Sink::from_u8(OUT.load(Ordering::Relaxed))
+}
+
+fn emit(sink: Sink, args: fmt::Arguments) {
+    if sink == Sink::Off {
+        return;
+    }
+    let buffered = BUFFER.with(|b| match b.borrow_mut().as_mut() {
+        Some(buf) => {
+            let _ = writeln!(buf, "{}", args);
+            true
+        },
+        None => false,
+    });
+    if !buffered {
+        match sink {
+            Sink::Stdout => println!("{}", args),
+            Sink::Stderr => eprintln!("{}", args),
+            Sink::Off => {},
+        }
+    }
+}
+
+pub fn log(args: fmt::Arguments) {
+    emit(sink(), args);
+}
+
+pub fn say(args: fmt::Arguments) {
+    emit(out(), args);
+}
+
+// run f, keeping everything it prints (on this thread) rather than printing it
+// note the buffered text is printed with flush(), so it all goes to the runner's sink
+pub fn buffered<R>(f: impl FnOnce() -> R) -> (R, String) {
+    BUFFER.with(|b| *b.borrow_mut() = Some(String::new()));
+    let r = f();
+    let text = BUFFER.with(|b| b.borrow_mut().take()).unwrap_or_default();
+    (r, text)
+}
+
+pub fn flush(text: &str) {
+    match out() {
+        Sink::Stdout => print!("{}", text),
+        Sink::Stderr => eprint!("{}", text),
+        Sink::Off => {},
+    }
+}
+
+// the arguments are only evaluated if the level is enabled
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Info) {
+            $crate::log::log(format_args!($($arg)*))
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::log(format_args!($($arg)*))
+        }
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::log(format_args!($($arg)*))
+        }
+    };
+}
+
+macro_rules! say {
+    ($($arg:tt)*) => {
+        $crate::log::say(format_args!($($arg)*))
+    };
+}
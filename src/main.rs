@@ -1,9 +1,10 @@
 // Advent of Code 2024
 // By david47k at d47 dot co
 
-use itertools::Itertools;
-
+#[macro_use]
+pub mod log;
 pub mod grid;
+pub mod sparse;
 pub mod vector;
 pub mod day01;
 pub mod day02;
@@ -34,163 +35,457 @@ pub mod time;
 pub mod level;
 pub mod defs;
 pub mod solve;
+pub mod cost;
+pub mod search;
+pub mod unionfind;
+pub mod shortcuts;
+pub mod render;
+pub mod image;
 pub mod stackstack;
+pub mod solver;
+pub mod error;
+pub mod manifest;
+pub mod json;
+pub mod bench;
 mod path;
 mod path2;
 mod pathtrait;
 mod obj;
 
+// what happened when a runsheet entry was run
+struct Outcome {
+    solver: String,
+    day: Option<usize>,
+    fname: String,
+    expected: Option<(String, String)>,
+    parts: solver::Parts,
+    result: Result<((String, String), solver::Timings), error::AocError>,
+}
+
+impl Outcome {
+    fn passed(&self) -> Option<bool> {
+        match (&self.expected, &self.result) {
+            (Some(e), Ok((r, _))) => Some(self.parts.fill(r, e) == *e),
+            (Some(_), Err(_)) => Some(false),
+            _ => None,
+        }
+    }
+    fn to_json(&self) -> json::JsonObject {
+        let (result, timings) = match &self.result {
+            Ok((r, t)) => (Some(r), Some(t)),
+            Err(_) => (None, None),
+        };
+        json::JsonObject::new()
+            .str("solver", &self.solver)
+            .opt_uint("day", self.day)
+            .str("input", &self.fname)
+            .opt_str("part1", result.filter(|_| self.parts.has(1)).map(|r| r.0.as_str()))
+            .opt_str("part2", result.filter(|_| self.parts.has(2)).map(|r| r.1.as_str()))
+            .opt_str("expected1", self.expected.as_ref().map(|e| e.0.as_str()))
+            .opt_str("expected2", self.expected.as_ref().map(|e| e.1.as_str()))
+            .opt_bool("pass", self.passed())
+            .opt_obj("time_ms", timings.map(|t| json::JsonObject::new()
+                .num("parse", t.parse)
+                .num("part1", t.part1)
+                .num("part2", t.part2)))
+            .opt_obj("error", self.result.as_ref().err().map(|e| json::JsonObject::new()
+                .str("message", &e.msg)
+                .opt_uint("line", e.line)
+                .opt_uint("col", e.col)))
+    }
+}
+
+// the entry for a day (or solver) given on the command line, with its input from a file, stdin ('-'),
+// or --input-str (where '\n' can be used for a newline)
+fn cli_entry(sel: &str, fname: Option<&str>, input_str: Option<&str>) -> error::AocResult<Option<manifest::Entry>> {
+    match (fname, input_str) {
+        (Some("-"), _) => {
+            let input = std::io::read_to_string(std::io::stdin()).map_err(|e| error::AocError::new(format!("unable to read stdin: {}", e)))?;
+            Ok(Some(manifest::Entry::with_input(sel, "<stdin>", input)))
+        },
+        (Some(fname), _) => Ok(Some(manifest::Entry::new(sel, fname))),
+        (None, Some(s)) => Ok(Some(manifest::Entry::with_input(sel, "<input-str>", s.replace("\\n", "\n")))),
+        (None, None) => Ok(None),
+    }
+}
+
+// the days selected by e.g. '5', '5..12', '5..=12', '20..' or '1,3,5..8' (ranges exclude the end, as in Rust, unless ..= is used)
+fn day_selection(s: &str) -> Option<Vec<std::ops::RangeInclusive<usize>>> {
+    s.split(',').map(|r| {
+        let day = |d: &str, default: usize| if d.is_empty() { Some(default) } else { d.parse::<usize>().ok() };
+        if let Some((a, b)) = r.split_once("..=") {
+            Some(day(a, 1)?..=day(b, 25)?)
+        } else if let Some((a, b)) = r.split_once("..") {
+            Some(day(a, 1)?..=day(b, 26)?.checked_sub(1)?)
+        } else {
+            let d = r.parse::<usize>().ok()?;
+            Some(d..=d)
+        }
+    }).collect()
+}
+
 fn main() {
-    println!("Advent of Code 2024");
-    println!("By david47k at d47 dot co");
+    let registry = solver::registry();
+
+    // runsheet entries are: solver (name or day number), input filename, expected result
+    let mut runsheet: Vec<manifest::Entry> = vec![];
 
-    let mut runsheet: Vec<(usize,String,(String,String))> = vec![];
+    // flags can go anywhere, the remaining args are positional
+    let mut args: Vec<String> = vec![];
+    let mut record = false;
+    let mut json = false;
+    let mut jobs: usize = 1;
+    let mut input_str: Option<String> = None;
+    let mut parts = solver::Parts::Both;
+    let mut cli_params = solver::Params::default();
+    let mut bench_opts = bench::BenchOptions::default();
+    let mut arg_iter = std::env::args();
+    while let Some(a) = arg_iter.next() {
+        if a == "--record" {
+            record = true;
+        } else if a == "-q" {
+            log::set_level(log::Level::Quiet);
+        } else if a == "-v" || a == "-vv" {
+            // -v shows debug output, and -vv (or -v -v) shows trace output as well
+            let level = match (a.as_str(), log::level()) {
+                ("-vv", _) | (_, log::Level::Debug | log::Level::Trace) => log::Level::Trace,
+                _ => log::Level::Debug,
+            };
+            log::set_level(level);
+        } else if a == "--render" {
+            render::set_render(true);
+        } else if a == "--fps" {
+            let v = arg_iter.next().unwrap_or_default();
+            match v.parse::<u32>() {
+                Ok(fps) => render::set_fps(fps),
+                Err(_) => {
+                    println!("Expected a number after --fps, found '{}'", v);
+                    return;
+                }
+            }
+        } else if a == "--image-dir" {
+            let Some(dir) = arg_iter.next() else {
+                println!("Expected a directory after --image-dir");
+                return;
+            };
+            image::set_dir(&dir);
+        } else if a == "--param" {
+            let kv = arg_iter.next().unwrap_or_default();
+            let Some((k, v)) = kv.split_once('=') else {
+                println!("Expected --param key=value, found '{}'", kv);
+                return;
+            };
+            cli_params.set(k, v);
+        } else if a == "--repeat" || a == "--threshold" || a == "--jobs" || a == "-j" {
+            let v = arg_iter.next().unwrap_or_default();
+            match (a.as_str(), v.parse::<usize>()) {
+                ("--repeat", Ok(n)) if n > 0 => bench_opts.repeat = n,
+                ("--jobs" | "-j", Ok(n)) if n > 0 => jobs = n,
+                ("--threshold", Ok(n)) => bench_opts.threshold = n as f64,
+                _ => {
+                    println!("Expected a number after {}, found '{}'", a, v);
+                    return;
+                }
+            }
+        } else if a == "--part" {
+            let v = arg_iter.next().unwrap_or_default();
+            match v.parse::<solver::Parts>() {
+                Ok(p) => parts = p,
+                Err(e) => {
+                    println!("--part: {}", e);
+                    return;
+                }
+            }
+        } else if a == "--input-str" {
+            let Some(s) = arg_iter.next() else {
+                println!("Expected the puzzle input after --input-str");
+                return;
+            };
+            input_str = Some(s);
+        } else if a == "--baseline" {
+            bench_opts.baseline = arg_iter.next().unwrap_or_default();
+        } else if a == "--save-baseline" {
+            bench_opts.save_baseline = true;
+        } else if a == "--format" {
+            match arg_iter.next().as_deref() {
+                Some("json") => json = true,
+                Some("text") => json = false,
+                f => {
+                    println!("Expected --format text or --format json, found '{}'", f.unwrap_or(""));
+                    return;
+                }
+            }
+        } else {
+            args.push(a);
+        }
+    }
 
-    let args: Vec<String> = std::env::args().collect();
+    // with --format json, stdout has one JSON object per runsheet entry, and everything else goes to stderr
+    if json {
+        log::set_sink(log::Sink::Stderr);
+        log::set_out(log::Sink::Stderr);
+    }
+    say!("Advent of Code 2024");
+    say!("By david47k at d47 dot co");
+    bench_opts.parts = parts;
 
-    let test = args.len() == 2 && args[1] == "test";
-    let test2 = args.len() == 2 && args[1] == "test2";
+    let test = args.len() >= 2 && args[1] == "test";
+    let test2 = args.len() >= 2 && args[1] == "test2";
+    let run_days = args.len() >= 2 && args[1] == "run";
     let mut tests_passed: usize = 0;
 
-    if test {
-        runsheet.append(&mut gen_test_data());
-    } else if test2 {
-        runsheet.append(&mut gen_test_data_2());
+    if args.len() == 2 && args[1] == "list" {
+        println!("\navailable solvers:");
+        for s in registry.iter() {
+            println!("  {:12} day {:2}", s.name(), s.day());
+            for p in s.params() {
+                println!("      --param {:18} {}", format!("{}={}", p.name, p.default), p.help);
+            }
+        }
+        return;
+    }
+
+    // benchmark a single day and input, or the entries in a manifest
+    if args.len() >= 2 && args[1] == "bench" {
+        let runsheet = if args.len() >= 4 || (args.len() == 3 && input_str.is_some()) {
+            match cli_entry(&args[2], args.get(3).map(|s| s.as_str()), input_str.as_deref()) {
+                Ok(entry) => entry.into_iter().collect(),
+                Err(e) => {
+                    println!("{}", e);
+                    return;
+                }
+            }
+        } else {
+            let path = args.get(2).map(|s| s.as_str()).unwrap_or("expected.txt");
+            match manifest::Manifest::load(path) {
+                Ok(m) => m.entries,
+                Err(e) => {
+                    println!("error in manifest {}: {}", path, e);
+                    return;
+                }
+            }
+        };
+        match bench::bench(&registry, &runsheet, &cli_params, &bench_opts) {
+            Ok(0) => {},
+            Ok(n) => {
+                println!("\n{} regression(s) against the baseline", n);
+                std::process::exit(1);
+            },
+            Err(e) => {
+                println!("{}", e);
+                std::process::exit(1);
+            },
+        }
+        return;
+    }
+
+    // 'run' takes the entries for some days from a manifest (by default, the examples)
+    let days = if run_days {
+        match args.get(2).and_then(|s| day_selection(s)) {
+            Some(days) => days,
+            None => {
+                say!("Specify the days to run, e.g. 'aoc24 run 5', 'aoc24 run 5..12' or 'aoc24 run 1,3,20..', and optionally a manifest");
+                return;
+            }
+        }
     } else {
-        if args.len() < 3 {
-            println!("Specify which day and input file as arguments (e.g. aoc24 1 ex01.txt)");
-            return;
+        vec![]
+    };
+
+    // the expected answers come from a manifest file, which can be given after 'test' or 'test2' (or 'run days')
+    let mut manifest: Option<manifest::Manifest> = None;
+    if test || test2 || run_days {
+        let path = args.get(if run_days { 3 } else { 2 }).map(|s| s.as_str()).unwrap_or(if test2 { "expected2.txt" } else { "expected.txt" });
+        match manifest::Manifest::load(path) {
+            Ok(m) => {
+                runsheet = m.entries.iter().filter(|e| {
+                    !run_days || registry.find(&e.sel).is_some_and(|s| days.iter().any(|d| d.contains(&s.day())))
+                }).cloned().collect();
+                manifest = Some(m);
+            },
+            Err(e) => {
+                say!("error in manifest {}: {}", path, e);
+                return;
+            }
         }
-        let day: usize = args[1].parse().expect("valid number for day");
-        let fname = args[2].parse().expect("valid input filename");
-        runsheet.push((day, fname, ("0".to_string(), "0".to_string())));
+    } else {
+        let entry = match args.get(1).map(|sel| cli_entry(sel, args.get(2).map(|s| s.as_str()), input_str.as_deref())) {
+            Some(Ok(entry)) => entry,
+            Some(Err(e)) => {
+                say!("{}", e);
+                return;
+            },
+            None => None,
+        };
+        let Some(entry) = entry else {
+            say!("Specify which day (or solver) and input file as arguments (e.g. aoc24 1 ex01.txt, or aoc24 day18-any ex18.txt)");
+            say!("The input file can be '-' to read stdin, or the input can be given with --input-str \"...\" instead");
+            say!("Use 'aoc24 list' to list the available solvers, and their parameters (set with --param key=value)");
+            say!("Use 'aoc24 test [manifest]' or 'aoc24 test2 [manifest]' to check answers, with --record to save the answers found");
+            say!("Use 'aoc24 run days [manifest]' to check some days (e.g. 'aoc24 run 5..12', where 12 is excluded, or 'aoc24 run 5..=12'), and --part 1 or --part 2 to solve only that part");
+            say!("Add --format json for one JSON object per entry on stdout, and --jobs N to run N days at once");
+            say!("Add -v for more detail from the solvers, -vv for all of it, or -q for none");
+            say!("Add --render to draw the solvers' pictures in colour, or --fps N to animate them too (e.g. days 6, 15 and 16)");
+            say!("Add --image-dir DIR to have the solvers write their pictures as PNG images in DIR (e.g. days 12, 14 and 16)");
+            say!("Use 'aoc24 bench [manifest]' or 'aoc24 bench day file' to time each entry, with --repeat N, --baseline file, --save-baseline and --threshold percent");
+            return;
+        };
+        runsheet.push(entry);
     }
+    let mut recorded: usize = 0;
+    let mut errored: usize = 0;
 
     let t0 = crate::time::get_time_ms();
+    let testing = test || test2 || run_days;
 
-    for (day,fname,eresult) in runsheet.iter() {
-        println!("\n--[ day {:02} running ]--------------------", day);
-
-        let input: String = std::fs::read_to_string(fname).expect("should be able to read file");
-
-        let result: (String, String) = match day {
-            1  => day01::day01(&input),
-            2  => day02::day02(&input),
-            3  => day03::day03(&input),
-            4  => day04::day04(&input),
-            5  => day05::day05(&input),
-            6  => day06::day06(&input),
-            7  => day07::day07(&input),
-            8  => day08::day08(&input),
-            9  => day09::day09(&input),
-            10 => day10::day10(&input),
-            11 => day11::day11(&input),
-            12 => day12::day12(&input),
-            13 => day13::day13(&input),
-            14 => day14::day14(&input),
-            15 => day15::day15(&input),
-            16 => day16::day16(&input),
-            17 => day17::day17(&input),
-            18 => day18::day18(&input),
-            19 => day19::day19(&input),
-            20 => day20::day20(&input),
-            21 => day21::day21(&input),
-            22 => day22::day22(&input),
-            23 => day23::day23(&input),
-            24 => day24::day24(&input),
-            25 => day25::day25(&input),
-            _  => { println!("Unknown day!"); ("no result".to_string(), "no result".to_string()) }
+    // run an entry (this may be on another thread)
+    let run = |entry: &manifest::Entry| -> Outcome {
+        let mut outcome = Outcome {
+            solver: entry.sel.clone(),
+            day: None,
+            fname: entry.fname.clone(),
+            expected: if testing { Some(entry.expected.clone()) } else { None },
+            parts,
+            result: Err(error::AocError::new("not run")),
         };
-        if test || test2 {
-            if result == *eresult {
-                tests_passed += 1;
-                println!("\n--[ day {:02} test passed ]---------------- ✔️", day);
-                println!("part 1: {}", result.0);
-                println!("part 2: {}", result.1);
-                println!();
-            } else {
-                println!("\n--[ day {:02} test failed ]---------------- ❌", day);
-                println!("expected: {:?}", *eresult);
-                println!("got:      {:?}", result);
-                println!();
-            }
+
+        let Some(solver) = registry.find(&entry.sel) else {
+            outcome.result = Err(error::AocError::new(format!("unknown day or solver '{}'", entry.sel)));
+            return outcome;
+        };
+        let day = solver.day();
+        outcome.solver = solver.name().to_string();
+        outcome.day = Some(day);
+        let default_solver = registry.for_day(day).map(|s| s.name()) == Some(solver.name());
+        if default_solver {
+            say!("\n--[ day {:02} running ]--------------------", day);
         } else {
-            println!("\n--[ day {:02} results ]--------------------", day);
-            println!("part 1: {}", result.0);
-            println!("part 2: {}", result.1);
+            say!("\n--[ day {:02} running ({}) ]--------------------", day, solver.name());
         }
+
+        // manifest params, overridden by --param (when testing, only those this solver knows about)
+        let mut params = solver::Params::new(entry.params.clone());
+        params.merge(&cli_params, if testing { Some(solver.params()) } else { None });
+
+        // a bad input is reported and we move on to the next entry
+        outcome.result = entry.run(solver, &params, parts);
+        outcome
+    };
+
+    // record and report the outcome of an entry (always in runsheet order, on this thread)
+    let mut finish = |idx: usize, outcome: Outcome| {
+        let (fname, eresult) = (&outcome.fname, &runsheet[idx].expected);
+        if outcome.result.is_err() {
+            errored += 1;
+        }
+        if let (Some(m), Ok((result, _))) = (manifest.as_mut().filter(|_| record), &outcome.result) {
+            if m.record(runsheet[idx].line, &parts.fill(result, eresult)) {
+                say!("\n--[ new answers for {} {} ]----------", outcome.solver, fname);
+                say!("was: {:?}", *eresult);
+                recorded += 1;
+            }
+        }
+
+        if outcome.passed() == Some(true) {
+            tests_passed += 1;
+        }
+
+        if json {
+            println!("{}", outcome.to_json());
+            return;
+        }
+
+        let day = outcome.day.unwrap_or(0);
+        match &outcome.result {
+            Err(e) if outcome.day.is_none() => {
+                println!("\n{}! Use 'aoc24 list' to list the available solvers.", e);
+            },
+            Err(e) => {
+                println!("\n--[ day {:02} error ]---------------------- ❌", day);
+                println!("error in {}: {}", fname, e);
+            },
+            Ok((result, timings)) => {
+                if testing {
+                    if outcome.passed() == Some(true) {
+                        println!("\n--[ day {:02} test passed ]---------------- ✔️", day);
+                        println!("part 1: {}", result.0);
+                        println!("part 2: {}", result.1);
+                        println!("{}", timings);
+                        println!();
+                    } else {
+                        println!("\n--[ day {:02} test failed ]---------------- ❌", day);
+                        println!("expected: {:?}", *eresult);
+                        println!("got:      {:?}", result);
+                        println!();
+                    }
+                } else {
+                    println!("\n--[ day {:02} results ]--------------------", day);
+                    println!("part 1: {}", result.0);
+                    println!("part 2: {}", result.1);
+                    println!("{}", timings);
+                }
+            },
+        }
+    };
+
+    if jobs <= 1 {
+        for (idx, entry) in runsheet.iter().enumerate() {
+            finish(idx, run(entry));
+        }
+    } else {
+        // each entry's output is buffered, and printed in runsheet order as the entries complete
+        // (animations aren't, so they are turned off, but the pictures still go in the buffered output)
+        if render::animating() {
+            say!("animations are off with --jobs {}, as they would clear the other days' output", jobs);
+            render::stop_animating();
+        }
+        let pool = match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+            Ok(pool) => pool,
+            Err(e) => {
+                say!("unable to start {} jobs: {}", jobs, e);
+                return;
+            }
+        };
+        let (tx, rx) = std::sync::mpsc::channel::<(usize, (Outcome, String))>();
+        std::thread::scope(|ts| {
+            let (run, runsheet) = (&run, &runsheet);
+            ts.spawn(move || pool.scope(|ps| {
+                for (idx, entry) in runsheet.iter().enumerate() {
+                    let tx = tx.clone();
+                    ps.spawn(move |_| {
+                        let _ = tx.send((idx, log::buffered(|| run(entry))));
+                    });
+                }
+            }));
+            let mut done: Vec<Option<(Outcome, String)>> = runsheet.iter().map(|_| None).collect();
+            let mut next = 0;
+            for (idx, r) in rx {
+                done[idx] = Some(r);
+                while let Some((outcome, text)) = done.get_mut(next).and_then(|d| d.take()) {
+                    log::flush(&text);
+                    finish(next, outcome);
+                    next += 1;
+                }
+            }
+        });
     }
     let t1 = crate::time::get_time_ms();
-    if test || test2 {
+    // the answers are only saved from a run with no errors, as a broken solver may well get the rest wrong too
+    if let Some(m) = manifest.as_ref().filter(|_| recorded > 0) {
+        if errored > 0 {
+            say!("\nnot recording {} new answer(s) in {}, as {} entries had errors", recorded, m.path, errored);
+        } else {
+            match m.save() {
+                Ok(()) => say!("\nrecorded {} new answer(s) in {}", recorded, m.path),
+                Err(e) => say!("\nunable to save {}: {}", m.path, e),
+            }
+        }
+    }
+    if testing {
         let ok = tests_passed == runsheet.len();
-        println!("\n--[ overall test results ]-------------- {}", if ok { "✔️" } else { "❌" } );
-        println!("\ntests conducted: {:2}", runsheet.len());
-        println!("tests failed   : {:2}", runsheet.len() - tests_passed);
+        say!("\n--[ overall test results ]-------------- {}", if ok { "✔️" } else { "❌" } );
+        say!("\ntests conducted: {:2}", runsheet.len());
+        say!("tests failed   : {:2}", runsheet.len() - tests_passed);
     }
-    println!("time: {:.3} s", (t1 - t0) / 1000.0);
-}
-
-fn gen_test_data() -> Vec<(usize,String,(String, String))> {
-    let d = vec![
-        ( 1,    "ex01.txt",   ("11", "31") ),
-        ( 2,    "ex02.txt",   ("2", "4") ),
-        ( 3,    "ex03p2.txt", ("161", "48") ),
-        ( 4,    "ex04.txt",   ("18", "9") ),
-        ( 5,    "ex05.txt",   ("143", "123") ),
-        ( 6,    "ex06.txt",   ("41", "6") ),
-        ( 7,    "ex07.txt",   ("3749", "11387") ),
-        ( 8,    "ex08.txt",   ("14", "34") ),
-        ( 9,    "ex09.txt",   ("1928", "2858") ),
-        ( 10,   "ex10.txt",   ("36", "81") ),
-        ( 11,   "ex11.txt",   ("55312", "65601038650482") ),
-        ( 12,   "ex12c.txt",  ("1930", "1206") ),
-        ( 13,   "ex13.txt",   ("480", "875318608908") ),
-        ( 14,   "ex14.txt",   ("12", "0") ),
-        ( 15,   "ex15.txt",   ("2028", "1751") ),
-        ( 15,   "ex15b.txt",  ("10092", "9021") ),
-        ( 15,   "ex15c.txt",  ("908", "618") ),
-        ( 16,   "ex16.txt",   ("7036", "45") ),
-        ( 16,   "ex16b.txt",  ("11048", "64") ),
-        ( 17,   "ex17b.txt",  ("5,7,3,0", "117440") ),
-        ( 18,   "ex18.txt",  ("146", "no solution found") ),
-        ( 19,   "ex19.txt",  ("6", "16") ),
-        ( 20,   "ex20.txt",  ("0", "0") ),  // not a great test
-        ( 21,   "ex21.txt",  ("126384", "154115708116294") ),
-        ( 22,   "ex22.txt",  ("37327623", "24") ),
-        ( 23,   "ex23.txt",  ("7", "co,de,ka,ta") ),
-        ( 24,   "ex24.txt",  ("2024", "unknown") ), // not a great test
-        ( 25,   "ex25.txt",  ("3", "no result") ),
-    ];
-    d.into_iter().map(|(day,fname,(result1,result2))| (day,fname.to_string(),(result1.to_string(), result2.to_string()))).collect_vec()
+    say!("time: {:.3} s", (t1 - t0) / 1000.0);
 }
-
-fn gen_test_data_2() -> Vec<(usize,String,(String, String))> {
-    let d = vec![
-        ( 1,    "input01.txt",   ("1223326", "21070419") ),
-        ( 2,    "input02.txt",   ("559", "601") ),
-        ( 3,    "input03.txt",   ("167650499", "95846796") ),
-        ( 4,    "input04.txt",   ("2613", "1905") ),
-        ( 5,    "input05.txt",   ("5208", "6732") ),
-        ( 6,    "input06.txt",   ("4789", "1304") ),
-        ( 7,    "input07.txt",   ("2664460013123", "426214131924213") ),
-        ( 8,    "input08.txt",   ("336", "1131") ),
-        ( 9,    "input09.txt",   ("6430446922192", "6460170593016") ),
-        ( 10,   "input10.txt",   ("694", "1497") ),
-        ( 11,   "input11.txt",   ("233875", "277444936413293") ),
-        ( 12,   "input12.txt",   ("1450816", "865662") ),
-        ( 13,   "input13.txt",   ("37686", "77204516023437") ),
-        ( 14,   "input14.txt",   ("219512160", "6398") ),
-        ( 15,   "input15.txt",   ("1552463", "1554058") ),
-        ( 16,   "input16.txt",   ("123540", "665") ),
-        ( 17,   "input17.txt",   ("1,2,3,1,3,2,5,3,1", "105706277661082") ),
-        ( 18,   "input18.txt",   ("436", "61,50") ),
-        ( 19,   "input19.txt",   ("263", "723524534506343") ),
-        ( 20,   "input20.txt",   ("1372", "979014") ),
-        ( 21,   "input21.txt",   ("155252", "195664513288128") ),
-        ( 22,   "input22.txt",   ("13461553007", "1499") ),
-        ( 23,   "input23.txt",   ("1098", "ar,ep,ih,ju,jx,le,ol,pk,pm,pp,xf,yu,zg") ),
-        ( 24,   "input24.txt",   ("55544677167336", "gsd,kth,qnf,tbt,vpm,z12,z26,z32") ), // not a great test
-        ( 25,   "input25.txt",   ("3255", "no result") ),
-    ];
-    d.into_iter().map(|(day,fname,(result1,result2))| (day,fname.to_string(),(result1.to_string(), result2.to_string()))).collect_vec()
-}
\ No newline at end of file
@@ -0,0 +1,122 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// manifest.rs: the expected answers for 'aoc24 test' and 'aoc24 test2', loaded from a text file
+//
+// One entry per line, fields separated by '|':
+//   solver | input file | part 1 | part 2 | params (optional, e.g. w=7 h=7)
+// The solver is a day number or a solver name. An answer of '?' means not known yet.
+// Blank lines and lines starting with '#' are ignored, and kept as-is when the manifest is saved.
+
+use crate::error::{AocError, AocResult};
+use crate::solver::{self, DynSolver, Params, Parts, Timings};
+
+pub const UNKNOWN: &str = "?";
+
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub line: usize,                        // 0-based line in the manifest file
+    pub sel: String,
+    pub fname: String,
+    pub expected: (String, String),
+    pub params: Vec<(String, String)>,
+    pub input: Option<String>,              // the input itself, when it came from stdin or --input-str
+}
+
+impl Entry {
+    pub fn new(sel: &str, fname: &str) -> Self {
+        Self {
+            line: 0,
+            sel: sel.to_string(),
+            fname: fname.to_string(),
+            expected: (UNKNOWN.to_string(), UNKNOWN.to_string()),
+            params: vec![],
+            input: None,
+        }
+    }
+    // an entry with its input given directly, rather than as a file (the name is just for display)
+    pub fn with_input(sel: &str, name: &str, input: String) -> Self {
+        Self {
+            input: Some(input),
+            ..Self::new(sel, name)
+        }
+    }
+    pub fn run(&self, solver: &dyn DynSolver, params: &Params, parts: Parts) -> AocResult<((String, String), Timings)> {
+        match &self.input {
+            Some(input) => solver::run_input(solver, input, params, parts),
+            None => solver::run_file(solver, &self.fname, params, parts),
+        }
+    }
+    pub fn to_line(&self) -> String {
+        let mut s = format!("{:9} | {:12} | {:17} | {}", self.sel, self.fname, self.expected.0, self.expected.1);
+        if !self.params.is_empty() {
+            let params = self.params.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<String>>().join(" ");
+            s = format!("{:66} | {}", s, params);
+        }
+        s.trim_end().to_string()
+    }
+}
+
+pub struct Manifest {
+    pub path: String,
+    lines: Vec<String>,
+    pub entries: Vec<Entry>,
+}
+
+impl Manifest {
+    pub fn load(path: &str) -> AocResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| AocError::new(format!("unable to read {}: {}", path, e)))?;
+        let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+        let mut entries = vec![];
+        for (y, l) in lines.iter().enumerate() {
+            let t = l.trim();
+            if t.is_empty() || t.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = t.split('|').map(|f| f.trim()).collect();
+            if fields.len() < 4 || fields.len() > 5 {
+                return Err(AocError::in_line(y, "expected 'solver | input file | part 1 | part 2 | params (optional)'"));
+            }
+            if let Some(i) = fields[..4].iter().position(|f| f.is_empty()) {
+                return Err(AocError::in_line(y, format!("field {} is empty", i + 1)));
+            }
+            let mut params = vec![];
+            if let Some(p) = fields.get(4) {
+                for kv in p.split_whitespace() {
+                    let Some((k, v)) = kv.split_once('=') else {
+                        return Err(AocError::in_line(y, format!("expected a parameter 'key=value', found '{}'", kv)));
+                    };
+                    params.push((k.to_string(), v.to_string()));
+                }
+            }
+            entries.push(Entry {
+                line: y,
+                sel: fields[0].to_string(),
+                fname: fields[1].to_string(),
+                expected: (fields[2].to_string(), fields[3].to_string()),
+                params,
+                input: None,
+            });
+        }
+        Ok(Self { path: path.to_string(), lines, entries })
+    }
+
+    // update the expected answers for the entry on a line, returning true if they changed
+    pub fn record(&mut self, line: usize, result: &(String, String)) -> bool {
+        let Some(e) = self.entries.iter_mut().find(|e| e.line == line) else {
+            return false;
+        };
+        if e.expected == *result {
+            return false;
+        }
+        e.expected = result.clone();
+        self.lines[e.line] = e.to_line();
+        true
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut s = self.lines.join("\n");
+        s.push('\n');
+        std::fs::write(&self.path, s)
+    }
+}
@@ -7,12 +7,12 @@ pub enum Obj {
 }
 
 impl Obj {
-    pub fn from_char(c: &char) -> Obj {
+    pub fn from_char(c: &char) -> Option<Obj> {
         match c {
-            '.' => Obj::Space,
-            '#' => Obj::Wall,
-            'd' => Obj::Deer,
-            _ => panic ! ("Invalid character"),
+            '.' => Some(Obj::Space),
+            '#' => Some(Obj::Wall),
+            'd' => Some(Obj::Deer),
+            _ => None,
         }
     }
     pub fn to_char(&self) -> char {
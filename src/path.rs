@@ -29,10 +29,10 @@ impl Move {
     }
     pub fn to_vector(&self) -> Vector {
         match self {
-            Move::Up    => Vector( 0, -1 ),
-            Move::Right => Vector( 1,  0 ),
-            Move::Down  => Vector( 0,  1 ),
-            Move::Left  => Vector(-1,  0 ),
+            Move::Up    => Vector::new( 0, -1 ),
+            Move::Right => Vector::new( 1,  0 ),
+            Move::Down  => Vector::new( 0,  1 ),
+            Move::Left  => Vector::new(-1,  0 ),
         }
     }
     pub fn from_u8_unchecked(n: u8) -> Move {
@@ -70,10 +70,10 @@ impl Move {
     }
     pub fn to_vector_sm(&self) -> VectorSm {
         match self {
-            Move::Up    => VectorSm( 0, -1 ),
-            Move::Right => VectorSm( 1,  0 ),
-            Move::Down  => VectorSm( 0,  1 ),
-            Move::Left  => VectorSm(-1,  0 ),
+            Move::Up    => VectorSm::new( 0, -1 ),
+            Move::Right => VectorSm::new( 1,  0 ),
+            Move::Down  => VectorSm::new( 0,  1 ),
+            Move::Left  => VectorSm::new(-1,  0 ),
         }
     }
     pub fn to_string(&self) -> String {
@@ -13,10 +13,10 @@ pub const ALLMOVES2: [Move2; 4] = [ Move2::Up, Move2::Right, Move2::Down, Move2:
 impl Move2 {
     pub fn to_vector(&self) -> Vector {
         match self {
-            Move2::Up    => Vector( 0, -1 ),
-            Move2::Right => Vector( 1,  0 ),
-            Move2::Down  => Vector( 0,  1 ),
-            Move2::Left  => Vector(-1,  0 ),
+            Move2::Up    => Vector::new( 0, -1 ),
+            Move2::Right => Vector::new( 1,  0 ),
+            Move2::Down  => Vector::new( 0,  1 ),
+            Move2::Left  => Vector::new(-1,  0 ),
         }
     }
     pub fn from_u8_unchecked(n: u8) -> Move2 {
@@ -37,10 +37,10 @@ impl Move2 {
     }
     pub fn to_vector_sm(&self) -> VectorSm {
         match self {
-            Move2::Up    => VectorSm( 0, -1 ),
-            Move2::Right => VectorSm( 1,  0 ),
-            Move2::Down  => VectorSm( 0,  1 ),
-            Move2::Left  => VectorSm(-1,  0 ),
+            Move2::Up    => VectorSm::new( 0, -1 ),
+            Move2::Right => VectorSm::new( 1,  0 ),
+            Move2::Down  => VectorSm::new( 0,  1 ),
+            Move2::Left  => VectorSm::new(-1,  0 ),
         }
     }
     pub fn to_string(&self) -> String {
@@ -205,7 +205,7 @@ impl ShrunkPath {
         } else if *pair[0] == pair[1].rotr().rotr() {
             return 2001;  // terrible choice, making a u-turn!
         } else {
-            println!("pair: {:?}", pair);
+            debug!("pair: {:?}", pair);
             panic!("shouldn't get here");
         }
     }
@@ -0,0 +1,183 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// render.rs: draw grids and levels in the terminal, in colour, with paths and points on top, and animate them
+//
+// Drawing is off unless asked for: --render draws the pictures a solver makes (e.g. the final state),
+// and --fps N also plays its animations at N frames a second (0 for as fast as the terminal can go),
+// unless days run in parallel (--jobs N), when only the pictures are drawn.
+// Pictures go where the runner's messages go (stdout, or stderr with --format json).
+// A solver only builds a picture when render::enabled(), so there's no cost otherwise.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+use crossterm::{cursor, queue, style::{self, Color, Stylize}, terminal};
+use crate::grid::Grid;
+use crate::level::Level;
+use crate::log::{self, Sink};
+use crate::vector::Vector;
+
+static RENDER: AtomicBool = AtomicBool::new(false);
+static ANIMATE: AtomicBool = AtomicBool::new(false);
+static FPS: AtomicU32 = AtomicU32::new(10);
+
+pub fn set_render(on: bool) {
+    RENDER.store(on, Ordering::Relaxed);
+}
+
+// animate at fps frames a second (which also turns drawing on)
+pub fn set_fps(fps: u32) {
+    FPS.store(fps, Ordering::Relaxed);
+    ANIMATE.store(true, Ordering::Relaxed);
+    set_render(true);
+}
+
+// play no animations (the pictures are still drawn), e.g. when days run in parallel, as the frames
+// are written straight to the terminal, and would clear the other days' output
+pub fn stop_animating() {
+    ANIMATE.store(false, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    RENDER.load(Ordering::Relaxed) && log::out() != Sink::Off
+}
+
+pub fn animating() -> bool {
+    enabled() && ANIMATE.load(Ordering::Relaxed)
+}
+
+// the colour of each class of cell, e.g. walls, boxes and robots
+#[derive(Clone, Debug)]
+pub struct Palette {
+    classes: Vec<(u8, Color)>,
+    other: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let mut p = Self { classes: vec![], other: Color::White };
+        p = p.with(b"#", Color::Grey).with(b".", Color::DarkGrey);
+        p = p.with(b"O[]", Color::Yellow).with(b"@^>v<dS", Color::Green).with(b"E", Color::Red);
+        p.with(b"0123456789", Color::Cyan)
+    }
+}
+
+impl Palette {
+    pub fn with(mut self, cells: &[u8], colour: Color) -> Self {
+        self.classes.retain(|c| !cells.contains(&c.0));
+        self.classes.extend(cells.iter().map(|&c| (c, colour)));
+        self
+    }
+    pub fn colour(&self, cell: u8) -> Color {
+        self.classes.iter().find(|c| c.0 == cell).map(|c| c.1).unwrap_or(self.other)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pixel {
+    ch: char,
+    fg: Color,
+    bg: Option<Color>,
+}
+
+// a coloured character for each cell, which can be drawn in the terminal
+#[derive(Clone, Debug)]
+pub struct Picture {
+    pub w: usize,
+    pub h: usize,
+    pixels: Vec<Pixel>,
+}
+
+impl Picture {
+    pub fn from_grid(grid: &Grid<u8>, palette: &Palette) -> Self {
+        let pixels = grid.data.iter().map(|&c| Pixel { ch: c as char, fg: palette.colour(c), bg: None }).collect();
+        Self { w: grid.w as usize, h: grid.h as usize, pixels }
+    }
+    pub fn from_level(level: &Level, palette: &Palette) -> Self {
+        let pixels = level.rows().iter().flat_map(|l| l.bytes()).map(|c| Pixel { ch: c as char, fg: palette.colour(c), bg: None }).collect();
+        Self { w: level.w, h: level.h, pixels }
+    }
+    fn pixel_mut(&mut self, v: Vector) -> Option<&mut Pixel> {
+        if v.0 < 0 || v.1 < 0 || v.0 as usize >= self.w || v.1 as usize >= self.h {
+            return None;
+        }
+        self.pixels.get_mut(v.1 as usize * self.w + v.0 as usize)
+    }
+    // draw ch at v, in colour (points outside the picture are ignored)
+    pub fn point(&mut self, v: Vector, ch: char, colour: Color) {
+        if let Some(p) = self.pixel_mut(v) {
+            *p = Pixel { ch, fg: colour, bg: p.bg };
+        }
+    }
+    // shade the cells along a path (or any set of cells), keeping what's in them
+    pub fn shade(&mut self, pts: &[Vector], colour: Color) {
+        for &v in pts {
+            if let Some(p) = self.pixel_mut(v) {
+                p.bg = Some(colour);
+            }
+        }
+    }
+    // the picture with ANSI colour codes, one line per row
+    pub fn to_styled(&self) -> String {
+        let mut s = String::new();
+        for row in self.pixels.chunks(self.w.max(1)) {
+            for p in row {
+                let c = style::style(p.ch).with(p.fg);
+                let c = match p.bg {
+                    Some(bg) => c.on(bg),
+                    None => c,
+                };
+                s += &c.to_string();
+            }
+            s += "\n";
+        }
+        s
+    }
+    // draw the picture (with a caption above it), if drawing is on
+    pub fn draw(&self, caption: &str) {
+        if enabled() {
+            say!("{}\n{}", caption, self.to_styled());
+        }
+    }
+}
+
+// plays pictures one after another, in the same place on the screen
+pub struct Animation {
+    delay: Duration,
+    frames: usize,
+}
+
+impl Animation {
+    // None unless animations are on (--fps)
+    pub fn new() -> Option<Self> {
+        if !animating() {
+            return None;
+        }
+        let fps = FPS.load(Ordering::Relaxed);
+        let delay = if fps == 0 { Duration::ZERO } else { Duration::from_secs_f64(1.0 / fps as f64) };
+        Some(Self { delay, frames: 0 })
+    }
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+    // clear the screen, draw the next frame, and wait until it's time for the one after
+    pub fn frame(&mut self, picture: &Picture, caption: &str) {
+        let text = format!("{}\n{}", caption, picture.to_styled());
+        let _ = match log::out() {
+            Sink::Stdout => show(&mut std::io::stdout(), &text),
+            Sink::Stderr => show(&mut std::io::stderr(), &text),
+            Sink::Off => Ok(()),
+        };
+        self.frames += 1;
+        std::thread::sleep(self.delay);
+    }
+}
+
+fn show(out: &mut impl Write, text: &str) -> std::io::Result<()> {
+    queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+    for line in text.lines() {
+        queue!(out, style::Print(line), cursor::MoveToNextLine(1))?;
+    }
+    out.flush()
+}
@@ -0,0 +1,304 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// search.rs: generic shortest path searches (BFS, Dijkstra and A*), over any kind of state
+//
+// The caller supplies the neighbours of a state (with the cost of each step, except for BFS),
+// and for A*, a heuristic, which must never overestimate the remaining cost.
+// A state just needs Ord (they are kept in a BTreeMap), e.g. a Vector for a position in a grid,
+// or (Vector, Move2) when the direction we are facing matters too.
+//
+// Every predecessor on a lowest cost path to a state is kept, so the distances also hold all the optimal
+// paths (as a DAG), which can be counted and listed. This needs the distances from a search without a goal,
+// so that every way of reaching the goal has been seen. Steps should cost more than 0 (CostModel makes sure of it),
+// as free steps can make the predecessors go round in a circle, and then the paths can't be counted.
+//
+// The _within searches can be given a budget: states more than max_depth steps from a start aren't expanded,
+// and the search gives up after expanding max_nodes states. Their result says whether the budget ran out,
+// so a caller can tell "there is no path" from "we gave up", and has the distances so far for a partial result.
+// (With a depth limit, dijkstra and A* can miss a path that costs more but has fewer steps.)
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
+
+// a path found by a search, from the start to the goal (inclusive)
+#[derive(Clone, Debug)]
+pub struct Path<S> {
+    pub states: Vec<S>,
+    pub cost: u64,
+}
+
+// how far a search may go
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Budget {
+    pub max_depth: u64,             // steps from a start
+    pub max_nodes: usize,           // states expanded
+}
+
+impl Budget {
+    pub const UNLIMITED: Budget = Budget { max_depth: u64::MAX, max_nodes: usize::MAX };
+}
+
+// the result of a search within a budget
+#[derive(Clone, Debug)]
+pub struct Search<S> {
+    pub dist: Distances<S>,
+    pub goal: Option<S>,            // the goal found (always None for the _distances searches)
+    pub expanded: usize,            // the states whose neighbours were looked at
+    pub truncated: bool,            // the budget ran out, so there may be states (and goals) not reached
+}
+
+impl<S: Ord + Clone> Search<S> {
+    pub fn path(&self) -> Option<Path<S>> {
+        self.dist.path_to(self.goal.as_ref()?)
+    }
+}
+
+// the lowest cost to reach each state from the start(s), and the states we could have come from
+#[derive(Clone, Debug)]
+pub struct Distances<S> {
+    dist: BTreeMap<S, (u64, Vec<S>)>,
+}
+
+impl<S: Ord + Clone> Distances<S> {
+    fn new() -> Self {
+        Self { dist: BTreeMap::new() }
+    }
+    pub fn get(&self, s: &S) -> Option<u64> {
+        self.dist.get(s).map(|d| d.0)
+    }
+    pub fn len(&self) -> usize {
+        self.dist.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.dist.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&S, u64)> {
+        self.dist.iter().map(|(s, d)| (s, d.0))
+    }
+    // the states before this one on the lowest cost paths to it (none for a start)
+    pub fn predecessors(&self, s: &S) -> &[S] {
+        self.dist.get(s).map(|d| d.1.as_slice()).unwrap_or(&[])
+    }
+    // a lowest cost path to a state, following where we came from back to a start
+    pub fn path_to(&self, goal: &S) -> Option<Path<S>> {
+        let cost = self.get(goal)?;
+        let mut states = vec![goal.clone()];
+        while let Some(prev) = self.predecessors(states.last().unwrap()).first() {
+            states.push(prev.clone());
+        }
+        states.reverse();
+        Some(Path { states, cost })
+    }
+    // the lowest cost of the goals reached, and the goals reached at that cost
+    pub fn best_of(&self, goals: impl IntoIterator<Item = S>) -> Option<(u64, Vec<S>)> {
+        let reached: Vec<(u64, S)> = goals.into_iter().filter_map(|g| Some((self.get(&g)?, g))).collect();
+        let best = reached.iter().map(|r| r.0).min()?;
+        Some((best, reached.into_iter().filter(|r| r.0 == best).map(|r| r.1).collect()))
+    }
+    // every state on a lowest cost path to the goals
+    pub fn on_optimal_paths(&self, goals: &[S]) -> BTreeSet<S> {
+        let mut states: BTreeSet<S> = BTreeSet::new();
+        let mut stack: Vec<S> = goals.iter().filter(|g| self.dist.contains_key(g)).cloned().collect();
+        while let Some(s) = stack.pop() {
+            if states.insert(s.clone()) {
+                stack.extend(self.predecessors(&s).iter().cloned());
+            }
+        }
+        states
+    }
+    // the number of different lowest cost paths to the goals, or None if there are more than a u128 can hold
+    // (or the predecessors aren't all closer to the start, which can't happen when every step costs more than 0)
+    pub fn count_paths(&self, goals: &[S]) -> Option<u128> {
+        // the predecessors of a state are always closer to the start, so we count from the start outwards
+        let mut states: Vec<S> = self.on_optimal_paths(goals).into_iter().collect();
+        states.sort_by_key(|s| self.get(s));
+        let mut counts: BTreeMap<S, u128> = BTreeMap::new();
+        for s in states {
+            let preds = self.predecessors(&s);
+            let n = if preds.is_empty() { 1 } else { preds.iter().try_fold(0u128, |n, p| n.checked_add(*counts.get(p)?))? };
+            counts.insert(s, n);
+        }
+        goals.iter().collect::<BTreeSet<&S>>().into_iter().filter_map(|g| counts.get(g)).try_fold(0u128, |n, c| n.checked_add(*c))
+    }
+    // each of the lowest cost paths to the goals, one at a time (there can be a great many)
+    pub fn paths<'a>(&'a self, goals: &[S]) -> Paths<'a, S> {
+        let mut goals: Vec<S> = goals.iter().filter(|g| self.dist.contains_key(g)).cloned().collect();
+        goals.reverse();
+        Paths { d: self, goals, stack: vec![] }
+    }
+}
+
+// iterator over the lowest cost paths to some goals, from Distances::paths()
+pub struct Paths<'a, S> {
+    d: &'a Distances<S>,
+    goals: Vec<S>,
+    stack: Vec<(S, usize)>,         // the path so far, from the goal back, and which predecessor we are following
+}
+
+impl<S: Ord + Clone> Iterator for Paths<'_, S> {
+    type Item = Path<S>;
+
+    fn next(&mut self) -> Option<Path<S>> {
+        if self.stack.is_empty() {
+            self.stack.push((self.goals.pop()?, 0));
+        }
+        // follow the current predecessors back to a start (or to a state already on the path, if steps were free)
+        loop {
+            let (s, i) = self.stack.last().unwrap();
+            let Some(prev) = self.d.predecessors(s).get(*i) else { break };
+            if self.stack.iter().any(|(t, _)| t == prev) {
+                break;
+            }
+            self.stack.push((prev.clone(), 0));
+        }
+        let states: Vec<S> = self.stack.iter().rev().map(|(s, _)| s.clone()).collect();
+        let cost = self.d.get(&self.stack[0].0).unwrap_or(0);
+
+        // then backtrack to the next predecessor not yet followed
+        self.stack.pop();
+        while let Some((s, i)) = self.stack.last_mut() {
+            *i += 1;
+            if *i < self.d.predecessors(s).len() {
+                break;
+            }
+            self.stack.pop();
+        }
+        Some(Path { states, cost })
+    }
+}
+
+// breadth first search, where every step costs 1
+pub fn bfs<S, I>(start: S, neighbours: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> Option<Path<S>>
+where S: Ord + Clone, I: IntoIterator<Item = S> {
+    breadth_first([start], neighbours, Some(is_goal), Budget::UNLIMITED).path()
+}
+
+// as bfs (from any of the starts), but giving up when the budget runs out
+pub fn bfs_within<S, I>(starts: impl IntoIterator<Item = S>, neighbours: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool, budget: Budget) -> Search<S>
+where S: Ord + Clone, I: IntoIterator<Item = S> {
+    breadth_first(starts, neighbours, Some(is_goal), budget)
+}
+
+// the number of steps to every state reachable from the starts
+pub fn bfs_distances<S, I>(starts: impl IntoIterator<Item = S>, neighbours: impl FnMut(&S) -> I) -> Distances<S>
+where S: Ord + Clone, I: IntoIterator<Item = S> {
+    breadth_first(starts, neighbours, None::<fn(&S) -> bool>, Budget::UNLIMITED).dist
+}
+
+// lowest cost path, where neighbours gives each next state and the cost of the step to it
+pub fn dijkstra<S, I>(start: S, neighbours: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> Option<Path<S>>
+where S: Ord + Clone, I: IntoIterator<Item = (S, u64)> {
+    best_first([start], neighbours, |_| 0, Some(is_goal), Budget::UNLIMITED).path()
+}
+
+// as dijkstra, but heading towards the goal first, guided by the heuristic
+pub fn astar<S, I>(start: S, neighbours: impl FnMut(&S) -> I, heuristic: impl FnMut(&S) -> u64, is_goal: impl FnMut(&S) -> bool) -> Option<Path<S>>
+where S: Ord + Clone, I: IntoIterator<Item = (S, u64)> {
+    best_first([start], neighbours, heuristic, Some(is_goal), Budget::UNLIMITED).path()
+}
+
+// as astar (from any of the starts), but giving up when the budget runs out
+pub fn astar_within<S, I>(starts: impl IntoIterator<Item = S>, neighbours: impl FnMut(&S) -> I, heuristic: impl FnMut(&S) -> u64, is_goal: impl FnMut(&S) -> bool, budget: Budget) -> Search<S>
+where S: Ord + Clone, I: IntoIterator<Item = (S, u64)> {
+    best_first(starts, neighbours, heuristic, Some(is_goal), budget)
+}
+
+// the lowest cost to every state reachable from the starts
+pub fn dijkstra_distances<S, I>(starts: impl IntoIterator<Item = S>, neighbours: impl FnMut(&S) -> I) -> Distances<S>
+where S: Ord + Clone, I: IntoIterator<Item = (S, u64)> {
+    best_first(starts, neighbours, |_| 0, None::<fn(&S) -> bool>, Budget::UNLIMITED).dist
+}
+
+// as dijkstra_distances, but giving up when the budget runs out
+pub fn dijkstra_distances_within<S, I>(starts: impl IntoIterator<Item = S>, neighbours: impl FnMut(&S) -> I, budget: Budget) -> Search<S>
+where S: Ord + Clone, I: IntoIterator<Item = (S, u64)> {
+    best_first(starts, neighbours, |_| 0, None::<fn(&S) -> bool>, budget)
+}
+
+// search until a goal is found (if there is a goal), or the budget runs out
+fn breadth_first<S, I>(starts: impl IntoIterator<Item = S>, mut neighbours: impl FnMut(&S) -> I, mut is_goal: Option<impl FnMut(&S) -> bool>, budget: Budget) -> Search<S>
+where S: Ord + Clone, I: IntoIterator<Item = S> {
+    let mut d = Distances::new();
+    let (mut expanded, mut truncated) = (0, false);
+    let mut queue: VecDeque<S> = VecDeque::new();
+    for s in starts {
+        if !d.dist.contains_key(&s) {
+            d.dist.insert(s.clone(), (0, vec![]));
+            queue.push_back(s);
+        }
+    }
+    while let Some(s) = queue.pop_front() {
+        if is_goal.as_mut().is_some_and(|f| f(&s)) {
+            return Search { dist: d, goal: Some(s), expanded, truncated };
+        }
+        if d.dist[&s].0 >= budget.max_depth {
+            truncated = true;
+            continue;
+        }
+        if expanded >= budget.max_nodes {
+            truncated = true;
+            break;
+        }
+        expanded += 1;
+        let steps = d.dist[&s].0 + 1;
+        for n in neighbours(&s) {
+            match d.dist.get_mut(&n) {
+                None => {
+                    d.dist.insert(n.clone(), (steps, vec![s.clone()]));
+                    queue.push_back(n);
+                },
+                Some((c, preds)) if *c == steps && !preds.contains(&s) => preds.push(s.clone()),
+                _ => {},
+            }
+        }
+    }
+    Search { dist: d, goal: None, expanded, truncated }
+}
+
+// dijkstra, or A* with a heuristic (which is 0 for dijkstra)
+fn best_first<S, I>(starts: impl IntoIterator<Item = S>, mut neighbours: impl FnMut(&S) -> I, mut heuristic: impl FnMut(&S) -> u64, mut is_goal: Option<impl FnMut(&S) -> bool>, budget: Budget) -> Search<S>
+where S: Ord + Clone, I: IntoIterator<Item = (S, u64)> {
+    let mut d = Distances::new();
+    let (mut expanded, mut truncated) = (0, false);
+    let mut heap: BinaryHeap<Reverse<(u64, u64, u64, S)>> = BinaryHeap::new();     // (estimated total cost, cost so far, steps, state)
+    for s in starts {
+        if !d.dist.contains_key(&s) {
+            d.dist.insert(s.clone(), (0, vec![]));
+            heap.push(Reverse((heuristic(&s), 0, 0, s)));
+        }
+    }
+    while let Some(Reverse((_, cost, depth, s))) = heap.pop() {
+        if cost > d.dist[&s].0 {
+            continue;       // we have since found a cheaper way here
+        }
+        if is_goal.as_mut().is_some_and(|f| f(&s)) {
+            return Search { dist: d, goal: Some(s), expanded, truncated };
+        }
+        if depth >= budget.max_depth {
+            truncated = true;
+            continue;
+        }
+        if expanded >= budget.max_nodes {
+            truncated = true;
+            break;
+        }
+        expanded += 1;
+        for (n, step) in neighbours(&s) {
+            let ncost = cost + step;
+            match d.dist.get_mut(&n) {
+                Some((c, preds)) if ncost == *c => {
+                    if !preds.contains(&s) {
+                        preds.push(s.clone());      // another way to get here, just as good
+                    }
+                },
+                Some((c, _)) if ncost > *c => {},
+                _ => {
+                    d.dist.insert(n.clone(), (ncost, vec![s.clone()]));
+                    heap.push(Reverse((ncost + heuristic(&n), ncost, depth + 1, n)));
+                },
+            }
+        }
+    }
+    Search { dist: d, goal: None, expanded, truncated }
+}
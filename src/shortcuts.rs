@@ -0,0 +1,79 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// shortcuts.rs: which shortcuts (cheats) through the walls of a level would make the path from start to end shorter
+//
+// A shortcut goes from one open cell to another, up to K steps away (Manhattan distance), ignoring walls.
+// With the distance from the start to every cell, and from every cell to the end, the length of the path
+// using a shortcut from a to b is just from_start[a] + steps + to_end[b], so this works for any maze,
+// not only one with a single corridor.
+
+use std::collections::BTreeMap;
+use crate::grid::Grid;
+use crate::level::Level;
+use crate::path2::ALLMOVES2;
+use crate::search;
+use crate::vector::Vector;
+
+pub const UNREACHABLE: u64 = u64::MAX;
+
+pub struct DistanceFields {
+    pub from_start: Grid<u64>,      // from the nearest start (UNREACHABLE for walls, and cells we can't get to)
+    pub to_end: Grid<u64>,          // to the nearest goal
+    pub best: Option<u64>,          // the shortest path from start to end, without shortcuts
+}
+
+// the number of shortcuts found, and how many save each amount
+#[derive(Clone, Debug, Default)]
+pub struct Shortcuts {
+    pub count: usize,
+    pub savings: BTreeMap<u64, usize>,
+}
+
+// the number of steps from the nearest of some cells to every cell reachable from them
+fn distance_field(level: &Level, from: &[Vector]) -> Grid<u64> {
+    let d = search::bfs_distances(from.iter().copied(), |&p| ALLMOVES2.iter().map(move |m| p.apply_dir(m)).filter(|&n| level.has_space_at(n)));
+    let mut field: Grid<u64> = Grid::new_with(level.w as i32, level.h as i32, UNREACHABLE);
+    for (p, steps) in d.iter() {
+        field.put(p, steps);
+    }
+    field
+}
+
+pub fn distance_fields(level: &Level) -> DistanceFields {
+    let from_start = distance_field(level, &level.starts);
+    let to_end = distance_field(level, &level.goals);
+    let best = level.goals.iter().filter_map(|g| from_start.get(g)).filter(|&d| d != UNREACHABLE).min();
+    DistanceFields { from_start, to_end, best }
+}
+
+// every shortcut of up to max_steps that saves at least min_savings steps
+pub fn find_shortcuts(fields: &DistanceFields, max_steps: i32, min_savings: u64) -> Shortcuts {
+    let mut found = Shortcuts::default();
+    let Some(best) = fields.best else {
+        return found;
+    };
+    // the offsets within max_steps (shortcuts of 0 or 1 steps can't go through a wall)
+    let offsets: Vec<(Vector, u64)> = (-max_steps..=max_steps).flat_map(|dy| {
+        let dx_max = max_steps - dy.abs();
+        (-dx_max..=dx_max).map(move |dx| (Vector::new(dx, dy), (dx.abs() + dy.abs()) as u64))
+    }).filter(|&(_, steps)| steps >= 2).collect();
+
+    for (a, &da) in fields.from_start.iter() {
+        if da == UNREACHABLE || da + 2 + min_savings.max(1) > best {
+            continue;
+        }
+        for &(offset, steps) in offsets.iter() {
+            let Some(db) = fields.to_end.get(&a.add(&offset)) else { continue };
+            if db == UNREACHABLE {
+                continue;
+            }
+            let len = da + steps + db;
+            if len < best && best - len >= min_savings {
+                found.count += 1;
+                *found.savings.entry(best - len).or_insert(0) += 1;
+            }
+        }
+    }
+    found
+}
@@ -1,315 +1,138 @@
 
 use crate::level::{Level};
 use crate::vector::{*};
-use crate::path2::{Move2,ShrunkPath,ALLMOVES2};
-
-use std::collections::{BTreeMap};
-use itertools::Itertools;
+use crate::path2::{Move2,ALLMOVES2};
+use crate::grid::NDIRS;
+use crate::cost::CostModel;
+use crate::search;
+use crate::log;
 
 #[derive(Clone, Debug)]
 pub struct Solution {
-	pub score: u64,
-	pub path: Vec<Move2>,
+	pub score: u64,					// the cost of the path
+	pub path: Vec<Move2>,			// the orthogonal moves (diagonal moves can't be a Move2, so only pts has them)
+	pub pts: Vec<Vector>,			// the positions along the path, from start to end
 	pub visited: Vec<Vector>,
-	pub max_depth_hit: bool,
-}
-#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Debug)]
-struct NodeID {
-	pub p: Vector,    // deer position
-	pub d: Move2,    // deer direction
+	pub reached_end: bool,			// if false, the path only gets as close to the end as the search did
+	pub truncated: bool,			// the search ran out of budget (depth or nodes), so a path may exist that it didn't find
+	pub expanded: usize,			// the states the search expanded
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Debug)]
-struct NodeData {
-	pub s: u64,				// score
-	pub path: ShrunkPath,	// path to reach this point
-	pub pts: Vec<Vector>,
+impl Solution {
+	// why there's no path to the end, for an error message
+	pub fn failure(&self) -> String {
+		if self.truncated {
+			format!("gave up after expanding {} states, {} moves from the start at best", self.expanded, self.pts.len() - 1)
+		} else {
+			format!("no path exists ({} states expanded)", self.expanded)
+		}
+	}
 }
 
-pub fn find_best_path_16(level: &Level, max_depth: u64) -> Option<Solution> {
-	// find path from start_pos to end_pos
-	// using score as path weight
-	// we will store how we get to each square:
-	//   (position, direction)   point identifier (key)
-	//   (score)                 point weight
-	//   (path as shrunkpath)    path to use
-	// this way we can sort & choose optimum path
-	// we haven't been in a location unless it's the same position AND direction!
-	// we keep going until we can't find any more paths to visit
-	// we keep seperate track of the best path that reach the finish line
-	// we will keep track of depth --- so we don't go too deep
-	let mut depth: u64 = 0;
-	let mut nodes: BTreeMap<NodeID,NodeData> = BTreeMap::new();
-	let mut edge_nodes: Vec<(NodeID, NodeData)> = vec![];
-	let mut extra_nodes: Vec<(NodeID, NodeData)> = vec![];
-	let mut solutions: Vec<(NodeID, NodeData)> = vec![];
-	let first_node_id = NodeID { p: level.deer_pos, d: level.deer_dir };
-	let first_node_data = NodeData { s: 0, path: ShrunkPath::new(), pts: [level.deer_pos].to_vec() };
-	nodes.insert(first_node_id, first_node_data.clone() );
-	edge_nodes.push((first_node_id, first_node_data ));
-	while edge_nodes.len() > 0 && depth < max_depth {
-		//println!("depth: {}", depth);
-		// for each edgenode
-		for (id,data) in edge_nodes.iter() {
-			//println!("testing edgenode {:?} with score {}", id, data.s);
-
-			// is it a winner? save it if so
-			if id.p == level.end_pos {
-				//println!("\nsolution found with score {} at depth {}", data.s, depth);
-				//println!("path: {}", data.path.to_string());
-				if solutions.len() == 0 || data.s < solutions[0].1.s {
-					solutions = [(*id, data.clone())].to_vec();
-					println!("score {} found at depth {}", data.s, depth);
-				} else if data.s == solutions[0].1.s {
-					solutions.push((*id, data.clone()));
-					//println!("--> additional best solution!");
-				}
-			}
-
-			// get directions -- vector, object, score
-			let maybes = ALLMOVES2.iter().map(|&m| (m, id.p.apply_dir(&m)));
-			let maybes = maybes.filter(|(_m, p)| level.has_space_at(*p) );
-			let maybes = maybes.collect_vec();
-			let maybes = maybes.into_iter().map(|(m, p)| (m, p, data.s + ShrunkPath::calc_score(&m, &id.d)));                // move, pos, score
-			let maybes = maybes.collect_vec();
-			// we now know mps: Move, Pos, Score
-
-			//println!("{} directions found", maybes.len());
-
-			// remove any from our list that have better contenders in nodes
-			let maybes = maybes.into_iter().filter(|(m, p, s)| {
-				let key = NodeID { p: *p, d: *m };
-				if let Some(existing_data) = nodes.get_mut(&key) {
-					if *s < existing_data.s {
-						// lower score is better! override any existing data
-						let mut path = data.path.clone();
-						path.push(m);
-						existing_data.pts = level.get_path_pts(&path.to_path());
-						let val = NodeData { s: *s, path: path, pts: existing_data.pts.clone() };	// any path is OK
-						//nodes.insert(key, val.clone());			// update it with our awesome data
-						existing_data.path = path;
-						existing_data.s = *s;
-						extra_nodes.push((key, val));
-					} else if *s == existing_data.s {
-						// this is an ALTERNATE way of getting to this point
-						let mut path = data.path.clone();
-						path.push(m);
-
-						// add the points from our list, and dedup
-						// SLOW POINT. Definitely not the fastest way !!!!
-						let mut extra_pts = level.get_path_pts(&path.to_path());
-						if extra_pts.iter().any(|xp| !existing_data.pts.contains(xp)) {
-							existing_data.pts.append(&mut extra_pts);
-							existing_data.pts.sort();
-							existing_data.pts.dedup();
-							let val = NodeData { s: *s, path, pts: existing_data.pts.clone() };
-							extra_nodes.push((key, val));
-						}
-					}
-					return false;    // we've handled it
-				} else {
-					return true;    // keep it,we haven't seen it before
-				}
-			}).collect_vec();
+// the moves the deer can make from p, and where they lead
+fn open_moves(level: &Level, p: Vector) -> impl Iterator<Item = (Move2, Vector)> + '_ {
+	ALLMOVES2.iter().map(move |&m| (m, p.apply_dir(&m))).filter(|(_m, n)| level.has_space_at(*n))
+}
 
-			// insert the new ones
-			for (m, p, s) in maybes.into_iter() {
-				let key = NodeID { p: p, d: m };
-				let mut path = data.path.clone();
-				path.push(&m);
-				let val = NodeData { s: s, path, pts: level.get_path_pts(&path.to_path()) };
-				nodes.insert(key, val.clone());
-				extra_nodes.push((key, val));
-			}
-		}
-		edge_nodes = extra_nodes.clone();
-		extra_nodes.clear();
-		depth += 1;
-	}
-	if solutions.len() > 0 {
-		let path = solutions[0].1.path;
-		let score = solutions[0].1.s;
-		let pts = nodes.get(&solutions[0].0).unwrap().pts.clone();
-		//let paths = solutions.iter().map(|(_, data)| data.path.iter().map(|p| p.to_path()).collect_vec()).flatten().collect_vec();
-		Some( Solution { score, path: path.to_path(), visited: pts, max_depth_hit: false } ) // max_depth_hit not implemented
-	} else {
-		None
-	}
+// the moves taken along a path of positions
+fn path_moves(pts: &[Vector]) -> Vec<Move2> {
+	pts.windows(2).map(|w| *ALLMOVES2.iter().find(|m| w[0].apply_dir(m) == w[1]).expect("adjacent points")).collect()
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Debug)]
-struct NodeID18 {
-	pub p: Vector,    // deer position
+// the distance from p to the nearest goal, ignoring walls
+fn goal_distance(level: &Level, p: Vector) -> u64 {
+	level.goals.iter().map(|g| p.distance(g) as u64).min().unwrap_or(0)
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Debug)]
-struct NodeData18 {
-	pub s: u64,				// score
-	pub path: ShrunkPath,	// path to reach this point
+// when no goal was reached, the best we can do is the (cheapest) reached state nearest one
+fn nearest_end<S: Ord + Clone>(level: &Level, dist: &search::Distances<S>, pos: impl Fn(&S) -> Vector) -> Option<search::Path<S>> {
+	let (s, _) = dist.iter().min_by_key(|(s, cost)| (goal_distance(level, pos(s)), *cost))?;
+	dist.path_to(s)
 }
 
-pub fn find_best_path_18(level: &Level, max_depth: u64) -> Option<Solution> {
-	let mut depth: u64 = 0;
-	let mut max_depth_hit = false;
-	let mut nodes: BTreeMap<NodeID18,NodeData18> = BTreeMap::new();
-	let mut edge_nodes: Vec<(NodeID18, NodeData18)> = vec![];
-	let mut extra_nodes: Vec<(NodeID18, NodeData18)> = vec![];
-	let mut solutions: Vec<(NodeID18, NodeData18)> = vec![];
-	let first_node_id = NodeID18 { p: level.deer_pos };
-	let first_node_data = NodeData18 { s: 0, path: ShrunkPath::new() };
-	nodes.insert(first_node_id, first_node_data.clone() );
-	edge_nodes.push((first_node_id, first_node_data ));
-	while edge_nodes.len() > 0 && depth < max_depth {
-		// for each edgenode
-		for (id,data) in edge_nodes.iter() {
-			// is it a winner? save it if so
-			if id.p == level.end_pos {
-				//println!("\nsolution found with score {} at depth {}", data.s, depth);
-				//println!("path: {}", data.path.to_string());
+fn log_search<S: Ord + Clone>(search: &search::Search<S>, budget: search::Budget) {
+	debug!("{} states expanded, {} reached", search.expanded, search.dist.len());
+	if search.truncated {
+		debug!("search truncated (max depth {}, max nodes {})", budget.max_depth, budget.max_nodes);
+	}
+}
 
-				if solutions.len() == 0 || data.s < solutions[0].1.s {
-					solutions = [(*id, data.clone())].to_vec();
-					//println!("--> best solution so far!");
-				} else if data.s == solutions[0].1.s {
-					solutions.push((*id, data.clone()));
-					//println!("--> additional best solution!");
-				}
+// the best path from any start to any goal, with moves costed by the model (for day16, CostModel::reindeer())
+// visited is every tile on any of the best paths
+pub fn find_best_path(level: &Level, model: &CostModel, budget: search::Budget) -> Solution {
+	// we haven't been in a location unless it's the same position AND direction!
+	let starts = level.starts.iter().map(|&p| (p, level.deer_dir as usize * 2));
+	let fwd = search::dijkstra_distances_within(starts, |&(p, d)| {
+		model.dirs().filter_map(move |nd| {
+			let n = p.add(&NDIRS[nd]);
+			if !level.has_space_at(n) {
+				return None;
 			}
+			model.move_cost(d, nd, level.cell_at(n)).map(|c| ((n, nd), c))
+		})
+	}, budget);
+	log_search(&fwd, budget);
+	let (truncated, expanded) = (fwd.truncated, fwd.expanded);
+	let fwd = fwd.dist;
+
+	// we may arrive at a goal facing any direction
+	let Some((score, ends)) = fwd.best_of(level.goals.iter().flat_map(|&g| (0..8).map(move |d| (g, d)))) else {
+		let path = nearest_end(level, &fwd, |s| s.0).expect("the start is always reached");
+		return Solution { score: path.cost, path: vec![], pts: path.states.iter().map(|s| s.0).collect(), visited: vec![], reached_end: false, truncated, expanded };
+	};
+	let path = fwd.path_to(&ends[0]).expect("a path to a reached state");
+	debug!("score {} found with {} moves", score, path.states.len() - 1);
+	// counting (and listing) the best paths can take a while, so it's only done when it will be shown
+	if log::enabled(log::Level::Debug) {
+		log_best_paths(&fwd, &ends);
+	}
 
-			// get directions -- vector, object, score
-			let maybes = ALLMOVES2.iter().map(|&m| (m, id.p.apply_dir(&m)));
-			let maybes = maybes.filter(|(_m, p)| level.has_space_at(*p) );
-			let maybes = maybes.collect_vec();
-			let maybes = maybes.into_iter().map(|(m, p)| (m, p, data.s + 1));                // move, pos, score
-			let maybes = maybes.collect_vec();
+	// the tiles on any of the best paths
+	let mut tiles: Vec<Vector> = fwd.on_optimal_paths(&ends).iter().map(|s| s.0).collect();
+	tiles.dedup();
 
-			// remove any from our list that have better contenders in nodes
-			let maybes = maybes.into_iter().filter(|(m, p, s)| {
-				let key = NodeID18 { p: *p };
-				if let Some(existing_data) = nodes.get_mut(&key) {
-					if *s < existing_data.s {
-						// lower score is better! override any existing data
-						let mut path = data.path.clone();
-						path.push(m);
-						let val = NodeData18 { s: *s, path: path };	// any path is OK
-						//nodes.insert(key, val.clone());			// update it with our awesome data
-						existing_data.path = path;
-						existing_data.s = *s;
-						extra_nodes.push((key, val));
-					}
-					return false;    // we've handled it
-				} else {
-					return true;    // keep it,we haven't seen it before
-				}
-			}).collect_vec();
+	let moves = path.states.iter().skip(1).filter(|s| s.1 % 2 == 0).map(|s| Move2::from_u8_unchecked(s.1 as u8 / 2)).collect();
+	Solution { score, path: moves, pts: path.states.iter().map(|s| s.0).collect(), visited: tiles, reached_end: true, truncated, expanded }
+}
 
-			// insert the new ones
-			for (m, p, s) in maybes.into_iter() {
-				let key = NodeID18 { p: p };
-				let mut path = data.path.clone();
-				path.push(&m);
-				let val = NodeData18 { s: s, path };
-				nodes.insert(key, val.clone());
-				extra_nodes.push((key, val));
-			}
-		}
-		edge_nodes = extra_nodes.clone();
-		extra_nodes.clear();
-		depth += 1;
-		if depth >= max_depth {
-			max_depth_hit = true;
-		}
+fn log_best_paths(fwd: &search::Distances<(Vector, usize)>, ends: &[(Vector, usize)]) {
+	match fwd.count_paths(ends) {
+		Some(n) => debug!("{} paths have the best score", n),
+		None => debug!("more than {} paths have the best score", u128::MAX),
 	}
-	if solutions.len() > 0 {
-		let path = solutions[0].1.path;
-		let score = solutions[0].1.s;
-		let pts = level.get_path_pts(&path.to_path()).clone();
-		Some( Solution { score, path: path.to_path(), visited: pts, max_depth_hit } )
-	} else {
-		if depth==max_depth {
-			//println!("WARNING: hit max depth!");
+	if log::enabled(log::Level::Trace) {
+		for p in fwd.paths(ends).take(10) {
+			trace!("best path: {}", p.states.iter().skip(1).map(|s| dir_name(s.1)).collect::<Vec<_>>().join(" "));
 		}
-		None
 	}
 }
 
+// the name of a direction (an index into NDIRS)
+fn dir_name(d: usize) -> &'static str {
+	["U", "UR", "R", "DR", "D", "DL", "L", "UL"][d]
+}
 
-pub fn find_any_path_18(level: &Level, max_depth: u64) -> Option<Solution> {
-	let mut depth: u64 = 0;
-	let mut max_depth_hit = false;
-	let mut nodes: BTreeMap<NodeID18,NodeData18> = BTreeMap::new();
-	let mut edge_nodes: Vec<(NodeID18, NodeData18)> = vec![];
-	let mut extra_nodes: Vec<(NodeID18, NodeData18)> = vec![];
-	let mut solutions: Vec<(NodeID18, NodeData18)> = vec![];
-	let first_node_id = NodeID18 { p: level.deer_pos };
-	let first_node_data = NodeData18 { s: 0, path: ShrunkPath::new() };
-	nodes.insert(first_node_id, first_node_data.clone() );
-	edge_nodes.push((first_node_id, first_node_data ));
-	while edge_nodes.len() > 0 && depth < max_depth {
-		// for each edgenode
-		for (id,data) in edge_nodes.iter() {
-			// is it a winner? save it if so
-			if id.p == level.end_pos {
-				if solutions.len() == 0 || data.s < solutions[0].1.s {
-					solutions = [(*id, data.clone())].to_vec();
-					//println!("--> best solution so far!");
-					depth = max_depth;	// quit
-					break;
-				}
-			}
-
-			// get directions -- vector, object, score
-			let maybes = ALLMOVES2.iter().map(|&m| (m, id.p.apply_dir(&m)));
-			let maybes = maybes.filter(|(_m, p)| level.has_space_at(*p) );
-			let maybes = maybes.collect_vec();
-			let maybes = maybes.into_iter().map(|(m, p)| (m, p, data.s + 1));                // move, pos, score
-			let maybes = maybes.collect_vec();
+// a solution from a search over positions, or the best partial path if it didn't reach the end
+fn position_solution(level: &Level, search: search::Search<Vector>, budget: search::Budget) -> Solution {
+	log_search(&search, budget);
+	let (reached_end, path) = match search.path() {
+		Some(path) => (true, path),
+		None => {
+			trace!("no path found");
+			(false, nearest_end(level, &search.dist, |&p| p).expect("the start is always reached"))
+		},
+	};
+	Solution { score: path.cost, path: path_moves(&path.states), pts: path.states.clone(), visited: path.states, reached_end, truncated: search.truncated, expanded: search.expanded }
+}
 
-			// remove any from our list that have better contenders in nodes
-			let maybes = maybes.into_iter().filter(|(m, p, s)| {
-				let key = NodeID18 { p: *p };
-				if let Some(existing_data) = nodes.get_mut(&key) {
-					if *s < existing_data.s {
-						// lower score is better! override any existing data
-						let mut path = data.path.clone();
-						path.push(m);
-						let val = NodeData18 { s: *s, path: path };	// any path is OK
-						//nodes.insert(key, val.clone());			// update it with our awesome data
-						existing_data.path = path;
-						existing_data.s = *s;
-						extra_nodes.push((key, val));
-					}
-					return false;    // we've handled it
-				} else {
-					return true;    // keep it,we haven't seen it before
-				}
-			}).collect_vec();
+pub fn find_best_path_18(level: &Level, budget: search::Budget) -> Solution {
+	// every move scores 1, so a breadth first search finds the best path
+	let search = search::bfs_within(level.starts.iter().copied(), |&p| open_moves(level, p).map(|(_m, n)| n), |p| level.goals.contains(p), budget);
+	position_solution(level, search, budget)
+}
 
-			// insert the new ones
-			for (m, p, s) in maybes.into_iter() {
-				let key = NodeID18 { p: p };
-				let mut path = data.path.clone();
-				path.push(&m);
-				let val = NodeData18 { s: s, path };
-				nodes.insert(key, val.clone());
-				extra_nodes.push((key, val));
-			}
-		}
-		edge_nodes = extra_nodes.clone();
-		extra_nodes.clear();
-		depth += 1;
-		if depth >= max_depth {
-			max_depth_hit = true;
-		}
-	}
-	if solutions.len() > 0 {
-		let path = solutions[0].1.path;
-		let score = solutions[0].1.s;
-		Some( Solution { score, path: path.to_path(), visited: vec![], max_depth_hit } )
-	} else {
-		if depth==max_depth {
-			//println!("WARNING: hit max depth!");
-		}
-		None
-	}
-}
\ No newline at end of file
+pub fn find_any_path_18(level: &Level, budget: search::Budget) -> Solution {
+	// A*, heading straight for the exit until the walls get in the way
+	let search = search::astar_within(level.starts.iter().copied(), |&p| open_moves(level, p).map(|(_m, n)| (n, 1)), |&p| goal_distance(level, p), |p| level.goals.contains(p), budget);
+	position_solution(level, search, budget)
+}
@@ -0,0 +1,262 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// solver.rs: Solver trait, and the Registry that each dayNN module registers into
+//
+// A day can register more than one solver (e.g. using a different path search), each with a unique name.
+// The runner selects a solver either by name (e.g. "day18-any") or by day number (the first one registered).
+//
+// Problems with the puzzle input are returned as an AocError (with line and column), rather than panicking.
+//
+// Puzzle parameters (e.g. the grid size, which differs between the example and the real input) are declared
+// by each solver with a default, and can be set in the manifest or with --param key=value.
+
+use crate::error::{AocError, AocResult};
+use std::str::FromStr;
+use std::time::Instant;
+
+// a parameter a solver accepts, and its default
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Params {
+    values: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn new(values: Vec<(String, String)>) -> Self {
+        Self { values }
+    }
+    // set a value, replacing any existing value for the key
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.retain(|(k, _)| k != key);
+        self.values.push((key.to_string(), value.to_string()));
+    }
+    // set the values from other params, overriding ours (if declared is given, only for the keys it declares)
+    pub fn merge(&mut self, other: &Params, declared: Option<&[Param]>) {
+        for (k, v) in other.iter() {
+            if declared.is_none_or(|d| d.iter().any(|p| p.name == k)) {
+                self.set(k, v);
+            }
+        }
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &(String, String)> {
+        self.values.iter()
+    }
+    pub fn get<T: FromStr>(&self, key: &str) -> AocResult<T> {
+        let Some((_, v)) = self.values.iter().find(|(k, _)| k == key) else {
+            return Err(AocError::new(format!("parameter {} has no value", key)));
+        };
+        v.parse::<T>().map_err(|_| AocError::new(format!("invalid value for parameter {}: '{}'", key, v)))
+    }
+    // the defaults declared by a solver, overridden by these values
+    pub fn with_defaults(&self, declared: &[Param]) -> AocResult<Params> {
+        if let Some((k, _)) = self.values.iter().find(|(k, _)| !declared.iter().any(|p| p.name == k)) {
+            return Err(AocError::new(format!("unknown parameter '{}'", k)));
+        }
+        let mut params = Params::new(declared.iter().map(|p| (p.name.to_string(), p.default.to_string())).collect());
+        for (k, v) in self.values.iter() {
+            params.set(k, v);
+        }
+        Ok(params)
+    }
+}
+
+// how long each stage of a run took, in ms
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: f64,
+    pub part1: f64,
+    pub part2: f64,
+}
+
+impl Timings {
+    pub fn total(&self) -> f64 {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "time: parse {:.3} ms, part 1 {:.3} ms, part 2 {:.3} ms", self.parse, self.part1, self.part2)
+    }
+}
+
+// which parts of a puzzle to solve (--part 1 or --part 2), the other is reported as SKIPPED
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Parts {
+    #[default]
+    Both,
+    Part1,
+    Part2,
+}
+
+pub const SKIPPED: &str = "(skipped)";
+
+impl std::str::FromStr for Parts {
+    type Err = AocError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Parts::Part1),
+            "2" => Ok(Parts::Part2),
+            "both" => Ok(Parts::Both),
+            _ => Err(AocError::new(format!("expected part 1, 2 or both, found '{}'", s))),
+        }
+    }
+}
+
+impl Parts {
+    pub fn has(&self, part: usize) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Part1 => part == 1,
+            Parts::Part2 => part == 2,
+        }
+    }
+    // the answers found, with the skipped part filled in from the other answers (e.g. the expected ones)
+    pub fn fill(&self, result: &(String, String), other: &(String, String)) -> (String, String) {
+        (
+            if self.has(1) { result.0.clone() } else { other.0.clone() },
+            if self.has(2) { result.1.clone() } else { other.1.clone() },
+        )
+    }
+}
+
+pub trait Solver {
+    type Input;
+
+    fn name(&self) -> &'static str;     // unique id, e.g. "day16" or "day18-any"
+    fn day(&self) -> usize;
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+    fn parse(&self, input: &str, params: &Params) -> AocResult<Self::Input>;
+    fn part1(&self, input: &mut Self::Input) -> AocResult<String>;
+    fn part2(&self, input: &mut Self::Input) -> AocResult<String>;
+}
+
+// object safe version of Solver, so we can keep them all in the one registry (and share it between threads)
+pub trait DynSolver: Sync {
+    fn name(&self) -> &'static str;
+    fn day(&self) -> usize;
+    fn params(&self) -> &'static [Param];
+    fn run(&self, input: &str, params: &Params, parts: Parts) -> AocResult<((String, String), Timings)>;
+}
+
+impl<S: Solver + Sync> DynSolver for S {
+    fn name(&self) -> &'static str {
+        Solver::name(self)
+    }
+    fn day(&self) -> usize {
+        Solver::day(self)
+    }
+    fn params(&self) -> &'static [Param] {
+        Solver::params(self)
+    }
+    fn run(&self, input: &str, params: &Params, parts: Parts) -> AocResult<((String, String), Timings)> {
+        let params = params.with_defaults(Solver::params(self))?;
+        let ms = |t: Instant| t.elapsed().as_secs_f64() * 1000.0;
+        let t = Instant::now();
+        let mut parsed = self.parse(input, &params)?;
+        let parse = ms(t);
+        // each part works from the parsed input, so either can be skipped
+        let (p1, part1) = if parts.has(1) {
+            let t = Instant::now();
+            (self.part1(&mut parsed)?, ms(t))
+        } else {
+            (SKIPPED.to_string(), 0.0)
+        };
+        let (p2, part2) = if parts.has(2) {
+            let t = Instant::now();
+            (self.part2(&mut parsed)?, ms(t))
+        } else {
+            (SKIPPED.to_string(), 0.0)
+        };
+        Ok(((p1, p2), Timings { parse, part1, part2 }))
+    }
+}
+
+// run a solver on an input, reporting a bad input (or a panic, as a backstop) as an error
+pub fn run_input(solver: &dyn DynSolver, input: &str, params: &Params, parts: Parts) -> AocResult<((String, String), Timings)> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| solver.run(input, params, parts)))
+        .unwrap_or_else(|_| Err(AocError::new("solver panicked")))
+}
+
+pub fn run_file(solver: &dyn DynSolver, fname: &str, params: &Params, parts: Parts) -> AocResult<((String, String), Timings)> {
+    let input = std::fs::read_to_string(fname).map_err(|e| AocError::new(format!("unable to read {}: {}", fname, e)))?;
+    run_input(solver, &input, params, parts)
+}
+
+pub struct Registry {
+    solvers: Vec<Box<dyn DynSolver>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self {
+            solvers: vec![],
+        }
+    }
+    pub fn add<S: Solver + Sync + 'static>(&mut self, solver: S) {
+        if self.get(Solver::name(&solver)).is_some() {
+            panic!("solver {} registered twice", Solver::name(&solver));
+        }
+        self.solvers.push(Box::new(solver));
+    }
+    pub fn get(&self, name: &str) -> Option<&dyn DynSolver> {
+        self.solvers.iter().find(|s| s.name() == name).map(|s| s.as_ref())
+    }
+    pub fn for_day(&self, day: usize) -> Option<&dyn DynSolver> {
+        self.solvers.iter().find(|s| s.day() == day).map(|s| s.as_ref())
+    }
+    pub fn find(&self, sel: &str) -> Option<&dyn DynSolver> {
+        // accepts a solver name, or a day number for the default solver for that day
+        match sel.parse::<usize>() {
+            Ok(day) => self.for_day(day),
+            Err(_) => self.get(sel),
+        }
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &dyn DynSolver> {
+        self.solvers.iter().map(|s| s.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn registry() -> Registry {
+    let mut r = Registry::new();
+    crate::day01::register(&mut r);
+    crate::day02::register(&mut r);
+    crate::day03::register(&mut r);
+    crate::day04::register(&mut r);
+    crate::day05::register(&mut r);
+    crate::day06::register(&mut r);
+    crate::day07::register(&mut r);
+    crate::day08::register(&mut r);
+    crate::day09::register(&mut r);
+    crate::day10::register(&mut r);
+    crate::day11::register(&mut r);
+    crate::day12::register(&mut r);
+    crate::day13::register(&mut r);
+    crate::day14::register(&mut r);
+    crate::day15::register(&mut r);
+    crate::day16::register(&mut r);
+    crate::day17::register(&mut r);
+    crate::day18::register(&mut r);
+    crate::day19::register(&mut r);
+    crate::day20::register(&mut r);
+    crate::day21::register(&mut r);
+    crate::day22::register(&mut r);
+    crate::day23::register(&mut r);
+    crate::day24::register(&mut r);
+    crate::day25::register(&mut r);
+    r
+}
@@ -0,0 +1,115 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// sparse.rs: a grid with no fixed size, which only stores the cells that have been set
+//
+// Any position can be set, including negative ones, and the bounding box of the cells set is kept up to date,
+// so the grid can be turned into a (dense) Grid when it's wanted, e.g. for printing.
+// A wrapping grid is a torus of a fixed size instead: positions are taken modulo the size (day14's robots),
+// so anything can be put in it without bounds arithmetic.
+
+use std::collections::BTreeMap;
+use crate::grid::Grid;
+use crate::vector::Vector;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: BTreeMap<Vector, T>,
+    bounds: Option<(Vector, Vector)>,       // the smallest box (inclusive) holding every cell set
+    wrap: Option<Vector>,                   // the size, for a wrapping grid
+}
+
+impl<T: Clone> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self { cells: BTreeMap::new(), bounds: None, wrap: None }
+    }
+    // a w x h torus, where each position is taken modulo the size
+    pub fn wrapping(w: i32, h: i32) -> Self {
+        assert!(w > 0 && h > 0, "a wrapping grid needs a size");
+        Self { cells: BTreeMap::new(), bounds: None, wrap: Some(Vector::new(w, h)) }
+    }
+    // where xy is in this grid (the same place, unless it wraps)
+    pub fn wrap(&self, xy: &Vector) -> Vector {
+        match self.wrap {
+            Some(size) => Vector::new(xy.0.rem_euclid(size.0), xy.1.rem_euclid(size.1)),
+            None => *xy,
+        }
+    }
+    pub fn get(&self, xy: &Vector) -> Option<&T> {
+        self.cells.get(&self.wrap(xy))
+    }
+    pub fn contains(&self, xy: &Vector) -> bool {
+        self.cells.contains_key(&self.wrap(xy))
+    }
+    // set a cell, returning what was there before
+    pub fn put(&mut self, xy: &Vector, value: T) -> Option<T> {
+        let xy = self.wrap(xy);
+        self.grow(xy);
+        self.cells.insert(xy, value)
+    }
+    // the cell at xy, set to default first if it isn't set, e.g. *g.get_or_insert(&xy, 0) += 1
+    pub fn get_or_insert(&mut self, xy: &Vector, default: T) -> &mut T {
+        let xy = self.wrap(xy);
+        self.grow(xy);
+        self.cells.entry(xy).or_insert(default)
+    }
+    pub fn remove(&mut self, xy: &Vector) -> Option<T> {
+        let xy = self.wrap(xy);
+        let old = self.cells.remove(&xy)?;
+        // the box only shrinks if the cell was on its edge
+        if let Some((min, max)) = self.bounds {
+            if xy.0 == min.0 || xy.1 == min.1 || xy.0 == max.0 || xy.1 == max.1 {
+                self.bounds = None;
+                let keys: Vec<Vector> = self.cells.keys().copied().collect();
+                keys.into_iter().for_each(|k| self.grow(k));
+            }
+        }
+        Some(old)
+    }
+    fn grow(&mut self, xy: Vector) {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (Vector::new(min.0.min(xy.0), min.1.min(xy.1)), Vector::new(max.0.max(xy.0), max.1.max(xy.1))),
+            None => (xy, xy),
+        });
+    }
+    // the smallest box (min and max, inclusive) holding every cell set, or None if there are none
+    pub fn bounds(&self) -> Option<(Vector, Vector)> {
+        self.bounds
+    }
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    // each cell set, and its position, in row order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector, &T)> {
+        let mut cells: Vec<(Vector, &T)> = self.cells.iter().map(|(k, v)| (*k, v)).collect();
+        cells.sort_by_key(|(k, _)| (k.1, k.0));
+        cells.into_iter()
+    }
+    // the cells set within the box from min to max (inclusive)
+    pub fn count_within(&self, min: &Vector, max: &Vector) -> usize {
+        self.cells.keys().filter(|k| k.0 >= min.0 && k.0 <= max.0 && k.1 >= min.1 && k.1 <= max.1).count()
+    }
+    // as a dense grid, with empty where no cell is set, and the position of its top left cell
+    // (the whole torus for a wrapping grid, which is always at 0,0)
+    pub fn to_grid(&self, empty: T) -> (Grid<T>, Vector) {
+        let (min, max) = match (self.wrap, self.bounds) {
+            (Some(size), _) => (Vector::new(0, 0), Vector::new(size.0 - 1, size.1 - 1)),
+            (None, Some(b)) => b,
+            (None, None) => return (Grid::new_with(0, 0, empty), Vector::new(0, 0)),
+        };
+        let mut grid = Grid::new_with(max.0 - min.0 + 1, max.1 - min.1 + 1, empty);
+        for (xy, v) in self.cells.iter() {
+            grid.put_unchecked(&xy.sub(&min), v.clone());
+        }
+        (grid, min)
+    }
+}
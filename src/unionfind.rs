@@ -0,0 +1,49 @@
+// Advent of Code 2024
+// By david47k at d47 dot co
+//
+// unionfind.rs: disjoint sets of 0..n, which can be joined, with near constant time lookups
+// (union by size, with path halving)
+
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+    // the representative of the set a is in
+    pub fn find(&mut self, mut a: usize) -> usize {
+        while self.parent[a] != a {
+            self.parent[a] = self.parent[self.parent[a]];
+            a = self.parent[a];
+        }
+        a
+    }
+    // join the sets a and b are in, returning false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+    // the size of the set a is in
+    pub fn size_of(&mut self, a: usize) -> usize {
+        let a = self.find(a);
+        self.size[a]
+    }
+}
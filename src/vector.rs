@@ -4,31 +4,90 @@
 // vector.rs: has vector for points / moves / directions and paths
 //
 // A point and a direction can both be implemented as a Vector
+//
+// Vector2 and Vector3 work with any signed integer type (see Scalar), and have the usual operators, so a + b,
+// a - b, v * 3 and -v all work. Vector (i32) and VectorSm (i8) are the 2D ones used for grids and paths.
+// Converting to a bigger type (cast) can't fail; converting to a smaller one (try_cast) can.
 
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use crate::grid::Grid;
 use crate::path::Move;
 use crate::path2::Move2;
 
-#[derive(Clone, Copy, PartialEq, Ord, PartialOrd, Eq, Debug)]
-pub struct Vector (pub i32, pub i32);
+// the types a vector can be made of
+pub trait Scalar: Copy + Ord + Hash + Default + fmt::Debug + fmt::Display
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    fn abs(self) -> Self;
+    fn to_f64(self) -> f64;
+    fn as_usize(self) -> usize;
+}
 
-impl Vector {
-    pub fn new(x: i32, y: i32) -> Vector {
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn as_usize(self) -> usize {
+                self as usize
+            }
+        })*
+    };
+}
+
+impl_scalar!(i8, i16, i32, i64);
+
+#[derive(Clone, Copy, PartialEq, Ord, PartialOrd, Eq, Hash, Default, Debug)]
+pub struct Vector2<T> (pub T, pub T);
+
+#[derive(Clone, Copy, PartialEq, Ord, PartialOrd, Eq, Hash, Default, Debug)]
+pub struct Vector3<T> (pub T, pub T, pub T);
+
+pub type Vector = Vector2<i32>;
+pub type VectorSm = Vector2<i8>;
+
+impl<T: Scalar> Vector2<T> {
+    // (an alias can't be called like a tuple struct, so this is how to make a Vector, even in a const)
+    pub const fn new(x: T, y: T) -> Self {
         Self(x,y)
     }
-    pub fn add(&self, dir: &Vector) -> Self {
+    pub fn add(&self, dir: &Self) -> Self {
         Self(self.0 + dir.0, self.1 + dir.1)
     }
-    pub fn sub(&self, v: &Vector) -> Self {
+    pub fn sub(&self, v: &Self) -> Self {
         Self(self.0 - v.0, self.1 - v.1)
     }
-    pub fn distance(&self, v: &Vector) -> i32 {
+    // the Manhattan distance
+    pub fn distance(&self, v: &Self) -> T {
+        self.manhattan(v)
+    }
+    pub fn manhattan(&self, v: &Self) -> T {
         (self.0 - v.0).abs() + (self.1 - v.1).abs()
     }
+    // the number of king's moves (diagonals allowed)
+    pub fn chebyshev(&self, v: &Self) -> T {
+        (self.0 - v.0).abs().max((self.1 - v.1).abs())
+    }
+    pub fn euclidean(&self, v: &Self) -> f64 {
+        let (dx, dy) = ((self.0 - v.0).to_f64(), (self.1 - v.1).to_f64());
+        dx.hypot(dy)
+    }
+    pub fn dot(&self, v: &Self) -> T {
+        self.0 * v.0 + self.1 * v.1
+    }
     pub fn double(&self) -> Self {
-        Self(self.0 * 2, self.1 * 2)
+        Self(self.0 + self.0, self.1 + self.1)
     }
-    pub fn mul(&self, n: i32) -> Self {
+    pub fn mul(&self, n: T) -> Self {
         Self(self.0 * n, self.1 * n)
     }
     pub fn rotr(&self) -> Self {
@@ -37,106 +96,289 @@ impl Vector {
     pub fn rotl(&self) -> Self {
         Self(-self.1, self.0)
     }
-    pub fn scale_by(&self, n: i32) -> Self {
+    // turned by the matrix, e.g. Matrix2::rotation(1)
+    pub fn rotate(&self, m: &Matrix2<T>) -> Self {
+        *m * *self
+    }
+    pub fn scale_by(&self, n: T) -> Self {
         Self(self.0 * n, self.1 * n)
     }
-    pub fn eq(&self, a: &Vector) -> bool {
+    pub fn eq(&self, a: &Self) -> bool {
         self.0 == a.0 && self.1 == a.1
     }
     pub fn add_dir(&self, dir: &Move) -> Self {
+        let one = T::ONE;
         match dir {
-            Move::Up    => Self( self.0,   self.1-1 ),
-            Move::Right => Self( self.0+1, self.1   ),
-            Move::Down  => Self( self.0,   self.1+1 ),
-            Move::Left  => Self( self.0-1, self.1   ),
+            Move::Up    => Self( self.0,     self.1-one ),
+            Move::Right => Self( self.0+one, self.1     ),
+            Move::Down  => Self( self.0,     self.1+one ),
+            Move::Left  => Self( self.0-one, self.1     ),
         }
     }
     pub fn apply_dir(&self, dir: &Move2) -> Self {
+        let one = T::ONE;
         match dir {
-            Move2::Up    => Self( self.0,   self.1-1 ),
-            Move2::Right => Self( self.0+1, self.1   ),
-            Move2::Down  => Self( self.0,   self.1+1 ),
-            Move2::Left  => Self( self.0-1, self.1   ),
+            Move2::Up    => Self( self.0,     self.1-one ),
+            Move2::Right => Self( self.0+one, self.1     ),
+            Move2::Down  => Self( self.0,     self.1+one ),
+            Move2::Left  => Self( self.0-one, self.1     ),
         }
     }
     pub fn add_dir2(&self, dir: &Move) -> Self {
+        let two = T::ONE + T::ONE;
         match dir {
-            Move::Up    => Self( self.0,   self.1-2 ),
-            Move::Right => Self( self.0+2, self.1   ),
-            Move::Down  => Self( self.0,   self.1+2 ),
-            Move::Left  => Self( self.0-2, self.1   ),
+            Move::Up    => Self( self.0,     self.1-two ),
+            Move::Right => Self( self.0+two, self.1     ),
+            Move::Down  => Self( self.0,     self.1+two ),
+            Move::Left  => Self( self.0-two, self.1     ),
         }
     }
-    pub fn to_index(&self, width: u16) -> usize {
-        width as usize * (self.1 as usize) + (self.0 as usize)
+    pub fn to_index(&self, width: usize) -> usize {
+        width * self.1.as_usize() + self.0.as_usize()
     }
     pub fn to_usize(&self) -> (usize,usize) {
-        (self.0 as usize, self.1 as usize)
+        (self.0.as_usize(), self.1.as_usize())
     }
-    pub fn to_string(&self) -> String {
-        format!("({},{})",self.0,self.1)
+    // as a bigger (or the same) type, which can't fail
+    pub fn cast<U: Scalar + From<T>>(&self) -> Vector2<U> {
+        Vector2(U::from(self.0), U::from(self.1))
     }
-    pub fn is_valid(&self, grid: &Grid) -> bool {
-        self.0 >= 0 && self.0 < grid.w && self.1 >= 0 && self.1 < grid.h
+    // as a smaller type, or None if it doesn't fit
+    pub fn try_cast<U: Scalar + TryFrom<T>>(&self) -> Option<Vector2<U>> {
+        Some(Vector2(U::try_from(self.0).ok()?, U::try_from(self.1).ok()?))
+    }
+    // with a z, e.g. for a layer of a 3D space
+    pub fn extend(&self, z: T) -> Vector3<T> {
+        Vector3(self.0, self.1, z)
     }
 }
 
+impl Vector {
+    pub fn is_valid<T>(&self, grid: &Grid<T>) -> bool {
+        self.0 >= 0 && self.0 < grid.w && self.1 >= 0 && self.1 < grid.h
+    }
+}
 
-#[derive(Clone, Copy, PartialEq, Ord, PartialOrd, Eq)]
-pub struct VectorSm ( pub i8, pub i8 );
 impl VectorSm {
+    // truncates anything that doesn't fit in an i8
     pub fn fromv(v: &Vector) -> Self {
-        Self ( v.0 as i8, v.1 as i8 )
+        Self::new(v.0 as i8, v.1 as i8)
     }
     pub fn intov(&self) -> Vector {
-        Vector(self.0 as i32, self.1 as i32)
+        self.cast()
     }
-    pub fn new(x: i8, y: i8) -> Self {
-        Self (x,y)
+}
+
+impl<T: Scalar> Vector3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self(x, y, z)
     }
-    pub fn add(&self, dir: &Self) -> Self {
-        Self (self.0 + dir.0, self.1 + dir.1)
+    pub fn manhattan(&self, v: &Self) -> T {
+        (self.0 - v.0).abs() + (self.1 - v.1).abs() + (self.2 - v.2).abs()
     }
-    pub fn double(&self) -> Self {
-        Self (self.0 * 2, self.1 * 2)
+    pub fn chebyshev(&self, v: &Self) -> T {
+        (self.0 - v.0).abs().max((self.1 - v.1).abs()).max((self.2 - v.2).abs())
     }
-    pub fn mul(&self, n: i8) -> Self {
-        Self(self.0 * n, self.1 * n)
+    pub fn euclidean(&self, v: &Self) -> f64 {
+        let (dx, dy, dz) = ((self.0 - v.0).to_f64(), (self.1 - v.1).to_f64(), (self.2 - v.2).to_f64());
+        (dx * dx + dy * dy + dz * dz).sqrt()
     }
-    pub fn rotr(&self) -> Self {
-        Self(self.1, -self.0)
+    pub fn dot(&self, v: &Self) -> T {
+        self.0 * v.0 + self.1 * v.1 + self.2 * v.2
     }
-    pub fn rotl(&self) -> Self {
-        Self(-self.1, self.0)
+    pub fn cross(&self, v: &Self) -> Self {
+        Self(self.1 * v.2 - self.2 * v.1, self.2 * v.0 - self.0 * v.2, self.0 * v.1 - self.1 * v.0)
     }
-    pub fn add_dir(&self, dir: &Move) -> Self {
-        match dir {
-            Move::Up    => Self( self.0,   self.1-1 ),
-            Move::Right => Self( self.0+1, self.1   ),
-            Move::Down  => Self( self.0,   self.1+1 ),
-            Move::Left  => Self( self.0-1, self.1   ),
+    pub fn rotate(&self, m: &Matrix3<T>) -> Self {
+        *m * *self
+    }
+    pub fn cast<U: Scalar + From<T>>(&self) -> Vector3<U> {
+        Vector3(U::from(self.0), U::from(self.1), U::from(self.2))
+    }
+    pub fn try_cast<U: Scalar + TryFrom<T>>(&self) -> Option<Vector3<U>> {
+        Some(Vector3(U::try_from(self.0).ok()?, U::try_from(self.1).ok()?, U::try_from(self.2).ok()?))
+    }
+    // without the z
+    pub fn xy(&self) -> Vector2<T> {
+        Vector2(self.0, self.1)
+    }
+}
+
+// the operators, for both sizes (with a reference on the right too, as the methods above take)
+macro_rules! impl_ops {
+    ($v:ident, $($i:tt),+) => {
+        impl<T: Scalar> Add for $v<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                $v($(self.$i + rhs.$i),+)
+            }
+        }
+        impl<T: Scalar> Add<&$v<T>> for $v<T> {
+            type Output = Self;
+            fn add(self, rhs: &Self) -> Self {
+                self + *rhs
+            }
+        }
+        impl<T: Scalar> Sub for $v<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                $v($(self.$i - rhs.$i),+)
+            }
         }
+        impl<T: Scalar> Sub<&$v<T>> for $v<T> {
+            type Output = Self;
+            fn sub(self, rhs: &Self) -> Self {
+                self - *rhs
+            }
+        }
+        impl<T: Scalar> Mul<T> for $v<T> {
+            type Output = Self;
+            fn mul(self, n: T) -> Self {
+                $v($(self.$i * n),+)
+            }
+        }
+        impl<T: Scalar> Neg for $v<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $v($(-self.$i),+)
+            }
+        }
+        impl<T: Scalar> AddAssign for $v<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+        impl<T: Scalar> SubAssign for $v<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Vector2, 0, 1);
+impl_ops!(Vector3, 0, 1, 2);
+
+impl<T: fmt::Display> fmt::Display for Vector2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.0, self.1)
     }
-    pub fn add_dir2(&self, dir: &Move) -> Self {
-        match dir {
-            Move::Up    => Self( self.0,   self.1-2 ),
-            Move::Right => Self( self.0+2, self.1   ),
-            Move::Down  => Self( self.0,   self.1+2 ),
-            Move::Left  => Self( self.0-2, self.1   ),
+}
+
+impl<T: fmt::Display> fmt::Display for Vector3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{})", self.0, self.1, self.2)
+    }
+}
+
+impl<T> From<(T, T)> for Vector2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self(x, y)
+    }
+}
+
+impl<T> From<Vector2<T>> for (T, T) {
+    fn from(v: Vector2<T>) -> Self {
+        (v.0, v.1)
+    }
+}
+
+// the conversions that can't lose anything, so e.g. Vector::from(small) and let v: Vector = small.into() work
+macro_rules! impl_widen {
+    ($($from:ty => $to:ty),*) => {
+        $(impl From<Vector2<$from>> for Vector2<$to> {
+            fn from(v: Vector2<$from>) -> Self {
+                v.cast()
+            }
+        }
+        impl From<Vector3<$from>> for Vector3<$to> {
+            fn from(v: Vector3<$from>) -> Self {
+                v.cast()
+            }
+        })*
+    };
+}
+
+impl_widen!(i8 => i16, i8 => i32, i8 => i64, i16 => i32, i16 => i64, i32 => i64);
+
+// a 2x2 matrix, rows first, for turning (or reflecting) 2D vectors
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Matrix2<T> (pub [[T; 2]; 2]);
+
+impl<T: Scalar> Matrix2<T> {
+    pub fn identity() -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        Self([[i, o], [o, i]])
+    }
+    // quarter turns clockwise on the screen (y down), e.g. up to right; negative for anticlockwise
+    pub fn rotation(quarter_turns: i32) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        match quarter_turns.rem_euclid(4) {
+            0 => Self::identity(),
+            1 => Self([[o, -i], [i, o]]),
+            2 => Self([[-i, o], [o, -i]]),
+            _ => Self([[o, i], [-i, o]]),
         }
     }
-    pub fn to_index(&self, width: u16) -> usize {
-        width as usize * (self.1 as usize) + (self.0 as usize)
+}
+
+impl<T: Scalar> Mul<Vector2<T>> for Matrix2<T> {
+    type Output = Vector2<T>;
+    fn mul(self, v: Vector2<T>) -> Vector2<T> {
+        let m = self.0;
+        Vector2(m[0][0] * v.0 + m[0][1] * v.1, m[1][0] * v.0 + m[1][1] * v.1)
     }
-    pub fn to_usize(&self) -> (usize,usize) {
-        (self.0 as usize, self.1 as usize)
+}
+
+impl<T: Scalar> Mul for Matrix2<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+        Self(std::array::from_fn(|r| std::array::from_fn(|c| a[r][0] * b[0][c] + a[r][1] * b[1][c])))
     }
-    pub fn to_string(&self) -> String {
-        format!("({},{})",self.0,self.1)
+}
+
+// a 3x3 matrix, rows first, for turning 3D vectors
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Matrix3<T> (pub [[T; 3]; 3]);
+
+impl<T: Scalar> Matrix3<T> {
+    pub fn identity() -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        Self([[i, o, o], [o, i, o], [o, o, i]])
+    }
+    // quarter turns about an axis (0 for x, 1 for y, 2 for z), anticlockwise looking down the axis at the origin
+    pub fn rotation(axis: usize, quarter_turns: i32) -> Self {
+        let (o, i) = (T::ZERO, T::ONE);
+        let (c, s) = match quarter_turns.rem_euclid(4) {
+            0 => (i, o),
+            1 => (o, i),
+            2 => (-i, o),
+            _ => (o, -i),
+        };
+        match axis {
+            0 => Self([[i, o, o], [o, c, -s], [o, s, c]]),
+            1 => Self([[c, o, s], [o, i, o], [-s, o, c]]),
+            _ => Self([[c, -s, o], [s, c, o], [o, o, i]]),
+        }
     }
 }
 
+impl<T: Scalar> Mul<Vector3<T>> for Matrix3<T> {
+    type Output = Vector3<T>;
+    fn mul(self, v: Vector3<T>) -> Vector3<T> {
+        let row = |r: [T; 3]| r[0] * v.0 + r[1] * v.1 + r[2] * v.2;
+        Vector3(row(self.0[0]), row(self.0[1]), row(self.0[2]))
+    }
+}
 
+impl<T: Scalar> Mul for Matrix3<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+        Self(std::array::from_fn(|r| std::array::from_fn(|c| a[r][0] * b[0][c] + a[r][1] * b[1][c] + a[r][2] * b[2][c])))
+    }
+}
 
 
 // SuperShrunkPath stores part of the path as an index to existing path strings... thus allowing us to shorten the path data due to reuse... it'll use a lot more cpu though, to find matching path strings